@@ -0,0 +1,295 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::random::{standard_normal, uniform};
+
+/// How the samples of each class are laid out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    /// One Gaussian cluster with standard deviation `cluster_std` per class,
+    /// around centers drawn uniformly from `[-10, 10)` in every feature.
+    Blobs {
+        n_classes: usize,
+        n_features: usize,
+        cluster_std: f64,
+    },
+    /// Two interleaving half circles in the plane.
+    Moons,
+    /// A circle of radius `factor` inside the unit circle, one class each.
+    Circles { factor: f64 },
+    /// Points in `[-1, 1)²`, class `0` in the quadrants where both
+    /// coordinates share a sign and class `1` in the others.
+    Xor,
+    /// Two classes on either side of a random hyperplane through the origin,
+    /// at least `margin` apart before noise.
+    LinearlySeparable { n_features: usize, margin: f64 },
+}
+
+impl Shape {
+    pub fn n_classes(&self) -> usize {
+        match *self {
+            Shape::Blobs { n_classes, .. } => n_classes,
+            _ => 2,
+        }
+    }
+
+    pub fn n_features(&self) -> usize {
+        match *self {
+            Shape::Blobs { n_features, .. } | Shape::LinearlySeparable { n_features, .. } => {
+                n_features
+            }
+            _ => 2,
+        }
+    }
+}
+
+/// Labelled samples in a given [`Shape`], for testing classifiers and
+/// clustering.
+///
+/// `noise` adds Gaussian noise of that standard deviation to every feature,
+/// blurring the class boundaries; `weights` makes some classes rarer than
+/// others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationGenerator {
+    pub shape: Shape,
+    pub n_samples: usize,
+    pub noise: f64,
+    /// Relative class sizes, one per class; equal when unset. Missing
+    /// entries count as zero and extra ones are ignored.
+    pub weights: Option<Vec<f64>>,
+}
+
+impl ClassificationGenerator {
+    /// Balanced classes without noise.
+    pub fn new(shape: Shape, n_samples: usize) -> Self {
+        Self {
+            shape,
+            n_samples,
+            noise: 0.0,
+            weights: None,
+        }
+    }
+
+    pub fn noise(mut self, std: f64) -> Self {
+        self.noise = std;
+        self
+    }
+
+    pub fn weights(mut self, weights: Vec<f64>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    /// Number of samples of each class: `n_samples` split in proportion to
+    /// the weights, with the rounding remainder going to the classes that
+    /// lost the most to it.
+    pub fn class_counts(&self) -> Vec<usize> {
+        let k = self.shape.n_classes();
+        let weights: Vec<f64> = match &self.weights {
+            Some(weights) => (0..k)
+                .map(|c| weights.get(c).copied().unwrap_or(0.0).max(0.0))
+                .collect(),
+            None => vec![1.0; k],
+        };
+        let total: f64 = weights.iter().sum();
+        if k == 0 || total <= 0.0 {
+            return vec![0; k];
+        }
+
+        let shares: Vec<f64> = weights
+            .iter()
+            .map(|w| self.n_samples as f64 * w / total)
+            .collect();
+        let mut counts: Vec<usize> = shares.iter().map(|s| s.floor() as usize).collect();
+        let mut by_remainder: Vec<usize> = (0..k).collect();
+        by_remainder.sort_by(|&a, &b| {
+            let (ra, rb) = (shares[a].fract(), shares[b].fract());
+            rb.total_cmp(&ra)
+        });
+        let assigned: usize = counts.iter().sum();
+        for &c in by_remainder.iter().take(self.n_samples - assigned) {
+            counts[c] += 1;
+        }
+        counts
+    }
+
+    /// Draws the samples in a random order.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> ClassificationData {
+        let d = self.shape.n_features();
+        let centers: Vec<Vec<f64>> = match self.shape {
+            Shape::Blobs { n_classes, .. } => (0..n_classes)
+                .map(|_| (0..d).map(|_| uniform(rng, -10.0, 10.0)).collect())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let normal = match self.shape {
+            Shape::LinearlySeparable { .. } => random_unit_vector(d, rng),
+            _ => Vec::new(),
+        };
+
+        let mut samples: Vec<(Vec<f64>, usize)> = Vec::with_capacity(self.n_samples);
+        for (class, count) in self.class_counts().into_iter().enumerate() {
+            for _ in 0..count {
+                let mut point = match self.shape {
+                    Shape::Blobs { cluster_std, .. } => centers[class]
+                        .iter()
+                        .map(|c| c + cluster_std * standard_normal(rng))
+                        .collect(),
+                    Shape::Moons => {
+                        let t = uniform(rng, 0.0, PI);
+                        if class == 0 {
+                            vec![t.cos(), t.sin()]
+                        } else {
+                            vec![1.0 - t.cos(), 0.5 - t.sin()]
+                        }
+                    }
+                    Shape::Circles { factor } => {
+                        let radius = if class == 0 { 1.0 } else { factor };
+                        let angle = uniform(rng, 0.0, 2.0 * PI);
+                        vec![radius * angle.cos(), radius * angle.sin()]
+                    }
+                    Shape::Xor => {
+                        let sign_x = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+                        let sign_y = if class == 0 { sign_x } else { -sign_x };
+                        vec![
+                            sign_x * uniform(rng, 0.0, 1.0),
+                            sign_y * uniform(rng, 0.0, 1.0),
+                        ]
+                    }
+                    Shape::LinearlySeparable { margin, .. } => {
+                        // Move a uniform point so that its distance to the
+                        // hyperplane is in [margin / 2, margin / 2 + 1)
+                        let side = if class == 0 { -1.0 } else { 1.0 };
+                        let point: Vec<f64> = (0..d).map(|_| uniform(rng, -1.0, 1.0)).collect();
+                        let distance = side * (margin / 2.0 + uniform(rng, 0.0, 1.0));
+                        let shift = distance - dot(&point, &normal);
+                        point
+                            .iter()
+                            .zip(&normal)
+                            .map(|(x, n)| x + shift * n)
+                            .collect()
+                    }
+                };
+                if self.noise > 0.0 {
+                    for x in point.iter_mut() {
+                        *x += self.noise * standard_normal(rng);
+                    }
+                }
+                samples.push((point, class));
+            }
+        }
+        samples.shuffle(rng);
+
+        let (features, labels) = samples.into_iter().unzip();
+        ClassificationData {
+            features,
+            labels,
+            n_classes: self.shape.n_classes(),
+            centers,
+        }
+    }
+}
+
+/// Samples from a `ClassificationGenerator`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationData {
+    /// `n × d` feature rows.
+    pub features: Vec<Vec<f64>>,
+    /// Class index of every row.
+    pub labels: Vec<usize>,
+    pub n_classes: usize,
+    /// Cluster centers for `Shape::Blobs`, one per class; empty otherwise.
+    pub centers: Vec<Vec<f64>>,
+}
+
+fn random_unit_vector<R: Rng + ?Sized>(d: usize, rng: &mut R) -> Vec<f64> {
+    // Normal draws point in every direction with equal probability
+    let v: Vec<f64> = (0..d).map(|_| standard_normal(rng)).collect();
+    let norm = dot(&v, &v).sqrt();
+    if norm == 0.0 {
+        return v;
+    }
+    v.iter().map(|x| x / norm).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::rng_from_seed;
+
+    fn label_counts(data: &ClassificationData) -> Vec<usize> {
+        let mut counts = vec![0; data.n_classes];
+        for &label in &data.labels {
+            counts[label] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn class_counts_follow_the_weights() {
+        let blobs = Shape::Blobs {
+            n_classes: 3,
+            n_features: 2,
+            cluster_std: 1.0,
+        };
+        assert_eq!(
+            ClassificationGenerator::new(blobs, 10).class_counts(),
+            [4, 3, 3]
+        );
+        let weighted = ClassificationGenerator::new(blobs, 10).weights(vec![1.0, 2.0, 7.0]);
+        assert_eq!(weighted.class_counts(), [1, 2, 7]);
+        let rounded = ClassificationGenerator::new(Shape::Moons, 10).weights(vec![1.0, 2.0]);
+        assert_eq!(rounded.class_counts(), [3, 7]);
+        // A missing weight counts as zero
+        let missing = ClassificationGenerator::new(blobs, 9).weights(vec![1.0, 2.0]);
+        assert_eq!(missing.class_counts(), [3, 6, 0]);
+    }
+
+    #[test]
+    fn generated_labels_match_the_class_counts() {
+        let generator = ClassificationGenerator::new(Shape::Xor, 50).weights(vec![4.0, 1.0]);
+        let data = generator.generate(&mut rng_from_seed(Some(1)));
+        assert_eq!(data.features.len(), 50);
+        assert_eq!(label_counts(&data), generator.class_counts());
+        assert_eq!(label_counts(&data), [40, 10]);
+    }
+
+    #[test]
+    fn noiseless_shapes_put_classes_where_documented() {
+        let mut rng = rng_from_seed(Some(2));
+
+        let xor = ClassificationGenerator::new(Shape::Xor, 100).generate(&mut rng);
+        for (point, &label) in xor.features.iter().zip(&xor.labels) {
+            let same_sign = (point[0] >= 0.0) == (point[1] >= 0.0);
+            assert_eq!(label, usize::from(!same_sign));
+        }
+
+        let circles =
+            ClassificationGenerator::new(Shape::Circles { factor: 0.5 }, 100).generate(&mut rng);
+        for (point, &label) in circles.features.iter().zip(&circles.labels) {
+            let radius = point[0].hypot(point[1]);
+            let expected = if label == 0 { 1.0 } else { 0.5 };
+            assert!((radius - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn blobs_have_one_center_per_class() {
+        let shape = Shape::Blobs {
+            n_classes: 4,
+            n_features: 3,
+            cluster_std: 0.5,
+        };
+        let data = ClassificationGenerator::new(shape, 40).generate(&mut rng_from_seed(Some(3)));
+        assert_eq!(data.centers.len(), 4);
+        assert!(data.centers.iter().all(|center| center.len() == 3));
+        assert!(data.features.iter().all(|point| point.len() == 3));
+        assert_eq!(label_counts(&data), [10, 10, 10, 10]);
+    }
+}
@@ -0,0 +1,40 @@
+//! Synthetic datasets with a known ground truth.
+//!
+//! [`RegressionGenerator`] builds linear regression data with configurable
+//! features, coefficients, noise and outliers. [`ClassificationGenerator`]
+//! builds labelled blobs, moons, circles, XOR and linearly separable
+//! classes. The fixed-size helpers below predate both and back the CLI
+//! demo.
+
+pub mod classification;
+pub mod regression;
+
+pub use classification::{ClassificationData, ClassificationGenerator, Shape};
+pub use regression::{Noise, RegressionData, RegressionGenerator};
+
+use rand::Rng;
+
+pub fn generate_100_samples() -> Vec<f64> {
+    generate_100_samples_with_rng(&mut rand::thread_rng())
+}
+
+/// Like `generate_100_samples`, drawing from `rng` so the data can be repeated.
+pub fn generate_100_samples_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Vec<f64> {
+    let mut arr_x = Vec::new();
+    for _ in 0..100 {
+        let x = rng.gen_range(0.0..10.0); // Generates an integer in the range [0, 10)
+        arr_x.push(x);
+    }
+    arr_x
+}
+
+/// `y = 2x + 3` for every `x`, exactly. Superseded by `RegressionGenerator`,
+/// which adds noise; this stays noiseless for the demo.
+pub fn gen_y(arr_x: Vec<f64>) -> Vec<f64> {
+    let mut arr_y = Vec::new();
+    for &x in &arr_x {
+        let y = 2.0 * x + 3.0; // y = 2x + 3, without noise
+        arr_y.push(y);
+    }
+    arr_y
+}
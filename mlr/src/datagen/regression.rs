@@ -0,0 +1,246 @@
+use rand::Rng;
+use rand::seq::index;
+
+use crate::random::{standard_normal, uniform};
+
+/// Noise added to the noiseless target `x · w + b`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Noise {
+    #[default]
+    None,
+    /// Normal with standard deviation `std`.
+    Gaussian { std: f64 },
+    /// Uniform on `[-half_width, half_width)`.
+    Uniform { half_width: f64 },
+    /// Normal with standard deviation `std · |x · w + b|`, so the spread
+    /// grows with the size of the target.
+    Heteroscedastic { std: f64 },
+}
+
+impl Noise {
+    /// A noise draw for a sample whose noiseless target is `clean`.
+    pub fn sample<R: Rng + ?Sized>(&self, clean: f64, rng: &mut R) -> f64 {
+        match *self {
+            Noise::None => 0.0,
+            Noise::Gaussian { std } => std * standard_normal(rng),
+            Noise::Uniform { half_width } => uniform(rng, -half_width, half_width),
+            Noise::Heteroscedastic { std } => std * clean.abs() * standard_normal(rng),
+        }
+    }
+}
+
+/// Linear regression data `y = x · w + b + noise`, like scikit-learn's
+/// `make_regression`, but with uniform features and the ground truth
+/// returned alongside the samples.
+///
+/// Setting `n_informative` below `n_features` adds irrelevant features,
+/// which is what lasso should learn to ignore.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegressionGenerator {
+    pub n_samples: usize,
+    pub n_features: usize,
+    /// Every feature is drawn uniformly from `[low, high)`.
+    pub feature_range: (f64, f64),
+    /// True weights; drawn from `coefficient_range` when unset.
+    pub coefficients: Option<Vec<f64>>,
+    pub coefficient_range: (f64, f64),
+    /// When drawing weights, only the first `n_informative` features get a
+    /// non-zero one.
+    pub n_informative: Option<usize>,
+    pub intercept: f64,
+    pub noise: Noise,
+    /// Share of the samples whose target is moved by `±outlier_magnitude`
+    /// after the noise.
+    pub outlier_fraction: f64,
+    pub outlier_magnitude: f64,
+}
+
+impl RegressionGenerator {
+    /// Features in `[0, 10)`, weights drawn from `[-5, 5)`, no intercept,
+    /// noise or outliers.
+    pub fn new(n_samples: usize, n_features: usize) -> Self {
+        Self {
+            n_samples,
+            n_features,
+            feature_range: (0.0, 10.0),
+            coefficients: None,
+            coefficient_range: (-5.0, 5.0),
+            n_informative: None,
+            intercept: 0.0,
+            noise: Noise::None,
+            outlier_fraction: 0.0,
+            outlier_magnitude: 0.0,
+        }
+    }
+
+    pub fn feature_range(mut self, low: f64, high: f64) -> Self {
+        self.feature_range = (low, high);
+        self
+    }
+
+    /// Fixes the true weights, one per feature; this also sets `n_features`.
+    pub fn coefficients(mut self, coefficients: Vec<f64>) -> Self {
+        self.n_features = coefficients.len();
+        self.coefficients = Some(coefficients);
+        self
+    }
+
+    pub fn coefficient_range(mut self, low: f64, high: f64) -> Self {
+        self.coefficient_range = (low, high);
+        self
+    }
+
+    pub fn n_informative(mut self, n_informative: usize) -> Self {
+        self.n_informative = Some(n_informative);
+        self
+    }
+
+    pub fn intercept(mut self, intercept: f64) -> Self {
+        self.intercept = intercept;
+        self
+    }
+
+    pub fn noise(mut self, noise: Noise) -> Self {
+        self.noise = noise;
+        self
+    }
+
+    /// Turns `fraction` of the samples (rounded) into outliers.
+    pub fn outliers(mut self, fraction: f64, magnitude: f64) -> Self {
+        self.outlier_fraction = fraction;
+        self.outlier_magnitude = magnitude;
+        self
+    }
+
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> RegressionData {
+        let d = self.n_features;
+        let coefficients = match &self.coefficients {
+            Some(coefficients) => coefficients.clone(),
+            None => {
+                let (low, high) = self.coefficient_range;
+                let n_informative = self.n_informative.unwrap_or(d).min(d);
+                (0..d)
+                    .map(|j| {
+                        if j < n_informative {
+                            uniform(rng, low, high)
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        let (low, high) = self.feature_range;
+        let features: Vec<Vec<f64>> = (0..self.n_samples)
+            .map(|_| (0..d).map(|_| uniform(rng, low, high)).collect())
+            .collect();
+        let mut target: Vec<f64> = features
+            .iter()
+            .map(|row| {
+                let clean = row
+                    .iter()
+                    .zip(&coefficients)
+                    .map(|(x, w)| x * w)
+                    .sum::<f64>()
+                    + self.intercept;
+                clean + self.noise.sample(clean, rng)
+            })
+            .collect();
+
+        let n_outliers = (self.n_samples as f64 * self.outlier_fraction.clamp(0.0, 1.0)).round();
+        let mut outliers = index::sample(rng, self.n_samples, n_outliers as usize).into_vec();
+        outliers.sort_unstable();
+        for &i in &outliers {
+            let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+            target[i] += sign * self.outlier_magnitude;
+        }
+
+        RegressionData {
+            features,
+            target,
+            coefficients,
+            intercept: self.intercept,
+            outliers,
+        }
+    }
+}
+
+/// Samples from a `RegressionGenerator` and the truth they were drawn from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegressionData {
+    /// `n × d` feature rows.
+    pub features: Vec<Vec<f64>>,
+    pub target: Vec<f64>,
+    pub coefficients: Vec<f64>,
+    pub intercept: f64,
+    /// Indices of the samples turned into outliers, in increasing order.
+    pub outliers: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::rng_from_seed;
+
+    #[test]
+    fn noiseless_targets_follow_the_coefficients() {
+        let data = RegressionGenerator::new(50, 0)
+            .coefficients(vec![2.0, -1.0, 0.5])
+            .intercept(3.0)
+            .feature_range(-1.0, 1.0)
+            .generate(&mut rng_from_seed(Some(1)));
+        assert_eq!(data.coefficients, [2.0, -1.0, 0.5]);
+        assert_eq!(data.features.len(), 50);
+        for (row, &y) in data.features.iter().zip(&data.target) {
+            assert_eq!(row.len(), 3);
+            assert!(row.iter().all(|x| (-1.0..1.0).contains(x)));
+            let clean = 2.0 * row[0] - row[1] + 0.5 * row[2] + 3.0;
+            assert!((y - clean).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn drawn_coefficients_leave_uninformative_features_at_zero() {
+        let data = RegressionGenerator::new(10, 5)
+            .coefficient_range(1.0, 2.0)
+            .n_informative(2)
+            .generate(&mut rng_from_seed(Some(2)));
+        assert!(
+            data.coefficients[..2]
+                .iter()
+                .all(|w| (1.0..2.0).contains(w))
+        );
+        assert_eq!(data.coefficients[2..], [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn outliers_move_the_requested_share_of_targets() {
+        let generator = RegressionGenerator::new(40, 0)
+            .coefficients(vec![1.0])
+            .outliers(0.1, 100.0);
+        let data = generator.generate(&mut rng_from_seed(Some(3)));
+        assert_eq!(data.outliers.len(), 4);
+        assert!(data.outliers.windows(2).all(|pair| pair[0] < pair[1]));
+        for (i, (row, &y)) in data.features.iter().zip(&data.target).enumerate() {
+            let shift = (y - row[0]).abs();
+            if data.outliers.contains(&i) {
+                assert!((shift - 100.0).abs() < 1e-9);
+            } else {
+                assert!(shift < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_noise_stays_within_its_half_width() {
+        let noise = Noise::Uniform { half_width: 0.5 };
+        let mut rng = rng_from_seed(Some(4));
+        assert!((0..1000).all(|_| noise.sample(10.0, &mut rng).abs() <= 0.5));
+        assert_eq!(Noise::None.sample(10.0, &mut rng), 0.0);
+        assert_eq!(
+            Noise::Heteroscedastic { std: 1.0 }.sample(0.0, &mut rng),
+            0.0
+        );
+    }
+}
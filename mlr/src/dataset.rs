@@ -0,0 +1,693 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::error::DatasetError;
+
+/// Fields treated as missing in addition to the empty string (compared
+/// case-insensitively after trimming).
+pub const DEFAULT_MISSING_MARKERS: [&str; 5] = ["na", "n/a", "nan", "null", "?"];
+
+/// A column picked by header name or by zero-based position.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Name(String),
+    Index(usize),
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Self {
+        Column::Name(name)
+    }
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+/// What to do with rows that have a missing value in a selected column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingValues {
+    /// Fail with `DatasetError::MissingValues`.
+    Error,
+    /// Leave the row out of the dataset.
+    DropRows,
+    /// Keep the row and store `f64::NAN` for a missing feature, for an
+    /// imputer to fill in. A row whose target is missing is still left out,
+    /// since no model can learn from it.
+    Keep,
+}
+
+/// How to turn a CSV file into a `Dataset`.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Column to predict; `None` loads features only, e.g. for inference.
+    pub target: Option<Column>,
+    /// Feature columns in order, which must not include the target; `None`
+    /// uses every column except the target.
+    pub features: Option<Vec<Column>>,
+    pub delimiter: char,
+    /// Whether the first record is a header; `None` detects it.
+    pub has_header: Option<bool>,
+    pub missing: MissingValues,
+    pub missing_markers: Vec<String>,
+    /// Feature columns holding category names instead of numbers, each with
+    /// the categories known in advance. See `Dataset::categories`.
+    pub categorical: Vec<(Column, Vec<String>)>,
+}
+
+impl CsvOptions {
+    pub fn new(target: impl Into<Column>) -> Self {
+        Self {
+            target: Some(target.into()),
+            ..Self::without_target()
+        }
+    }
+
+    /// Options for a file that has no target column.
+    pub fn without_target() -> Self {
+        Self {
+            target: None,
+            features: None,
+            delimiter: ',',
+            has_header: None,
+            missing: MissingValues::Error,
+            missing_markers: DEFAULT_MISSING_MARKERS
+                .iter()
+                .map(|m| m.to_string())
+                .collect(),
+            categorical: Vec::new(),
+        }
+    }
+
+    pub fn features<C: Into<Column>>(mut self, columns: impl IntoIterator<Item = C>) -> Self {
+        self.features = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = Some(has_header);
+        self
+    }
+
+    pub fn missing(mut self, missing: MissingValues) -> Self {
+        self.missing = missing;
+        self
+    }
+
+    /// Reads `column` as category names rather than numbers.
+    pub fn categorical(self, column: impl Into<Column>) -> Self {
+        self.categories(column, Vec::new())
+    }
+
+    /// Reads `column` as category names, coding `categories` as `0, 1, ...`
+    /// in that order whether or not they occur, so a file gets the same
+    /// codes as the one a model was trained on.
+    pub fn categories(mut self, column: impl Into<Column>, categories: Vec<String>) -> Self {
+        self.categorical.push((column.into(), categories));
+        self
+    }
+
+    pub fn is_missing(&self, field: &str) -> bool {
+        let field = field.trim();
+        field.is_empty()
+            || self
+                .missing_markers
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(field))
+    }
+}
+
+/// Raw CSV contents: a header plus string records, before any column is
+/// interpreted as numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub records: Vec<Vec<String>>,
+    /// 1-based line on which each record starts, for error messages.
+    pub lines: Vec<usize>,
+}
+
+impl CsvTable {
+    /// Reads a CSV file. Fields may be quoted with `"`, and a doubled `""`
+    /// inside quotes is a literal quote. Blank lines are skipped. Without a
+    /// header, columns are named `column_0`, `column_1`, ...
+    pub fn from_reader<R: BufRead>(
+        reader: R,
+        delimiter: char,
+        has_header: Option<bool>,
+    ) -> Result<Self, DatasetError> {
+        let mut records = Vec::new();
+        let mut lines = Vec::new();
+        let mut pending: Option<(usize, String)> = None;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let (start, text) = match pending.take() {
+                Some((start, mut text)) => {
+                    text.push('\n');
+                    text.push_str(&line);
+                    (start, text)
+                }
+                None => (i + 1, line),
+            };
+
+            match split_record(&text, delimiter) {
+                Some(fields) => {
+                    if fields.len() == 1 && fields[0].trim().is_empty() {
+                        continue;
+                    }
+                    records.push(fields);
+                    lines.push(start);
+                }
+                // A quoted field continues on the next line
+                None => pending = Some((start, text)),
+            }
+        }
+
+        if let Some((line, _)) = pending {
+            return Err(DatasetError::UnterminatedQuote { line });
+        }
+        if records.is_empty() {
+            return Err(DatasetError::Empty);
+        }
+
+        let header_present = has_header.unwrap_or_else(|| looks_like_header(&records[0]));
+        let headers: Vec<String> = if header_present {
+            lines.remove(0);
+            records
+                .remove(0)
+                .into_iter()
+                .map(|h| h.trim().to_string())
+                .collect()
+        } else {
+            (0..records[0].len())
+                .map(|j| format!("column_{}", j))
+                .collect()
+        };
+
+        let expected = headers.len();
+        for (record, &line) in records.iter().zip(lines.iter()) {
+            if record.len() != expected {
+                return Err(DatasetError::RaggedRow {
+                    line,
+                    expected,
+                    found: record.len(),
+                });
+            }
+        }
+
+        Ok(Self {
+            headers,
+            records,
+            lines,
+        })
+    }
+
+    pub fn from_path(
+        path: impl AsRef<Path>,
+        delimiter: char,
+        has_header: Option<bool>,
+    ) -> Result<Self, DatasetError> {
+        let file = File::open(path)?;
+        Self::from_reader(BufReader::new(file), delimiter, has_header)
+    }
+
+    pub fn n_columns(&self) -> usize {
+        self.headers.len()
+    }
+
+    /// Resolves a `Column` to its zero-based index.
+    pub fn column_index(&self, column: &Column) -> Result<usize, DatasetError> {
+        match column {
+            Column::Name(name) => self
+                .headers
+                .iter()
+                .position(|h| h == name)
+                .ok_or_else(|| DatasetError::UnknownColumn(name.clone())),
+            Column::Index(index) if *index < self.n_columns() => Ok(*index),
+            Column::Index(index) => Err(DatasetError::ColumnOutOfRange {
+                index: *index,
+                n_columns: self.n_columns(),
+            }),
+        }
+    }
+}
+
+/// Splits one record, or returns `None` if it ends inside a quoted field.
+fn split_record(text: &str, delimiter: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(std::mem::take(&mut field));
+        } else if c != '\r' {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return None;
+    }
+    fields.push(field);
+
+    Some(fields)
+}
+
+// A first record where no field parses as a number is taken to be a header.
+fn looks_like_header(record: &[String]) -> bool {
+    record
+        .iter()
+        .all(|field| !field.trim().is_empty() && field.trim().parse::<f64>().is_err())
+}
+
+// The categories of every feature in `feature_indices`, empty for numeric
+// ones: the known ones from `options`, then the rest in sorted order.
+fn find_categories(
+    table: &CsvTable,
+    options: &CsvOptions,
+    feature_indices: &[usize],
+) -> Result<Vec<Vec<String>>, DatasetError> {
+    let mut categories = vec![Vec::new(); feature_indices.len()];
+    for (column, known) in &options.categorical {
+        let j = table.column_index(column)?;
+        let Some(k) = feature_indices.iter().position(|&f| f == j) else {
+            continue;
+        };
+
+        let found: BTreeSet<&str> = table
+            .records
+            .iter()
+            .map(|record| record[j].trim())
+            .filter(|field| !options.is_missing(field) && !known.iter().any(|c| c == field))
+            .collect();
+        categories[k] = known
+            .iter()
+            .cloned()
+            .chain(found.into_iter().map(String::from))
+            .collect();
+    }
+    Ok(categories)
+}
+
+/// Per-column count of missing values found while loading.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MissingValueReport {
+    /// `(column name, missing count)` for every selected column, features
+    /// first and the target last.
+    pub per_column: Vec<(String, usize)>,
+    /// 1-based lines of the rows that had at least one missing value.
+    pub lines: Vec<usize>,
+    /// How many of those rows were left out: all of them under
+    /// `MissingValues::DropRows`, those missing the target under `Keep`.
+    pub dropped_rows: usize,
+}
+
+impl MissingValueReport {
+    pub fn total(&self) -> usize {
+        self.per_column.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+/// Numeric features and target loaded for training.
+///
+/// `features` is `n_samples × n_features`, the layout `MultiLinRegModel`
+/// takes; `feature_column` gives the single column `LinRegModel` takes.
+/// Without a target column `target_name` is `None` and `target` is empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    pub feature_names: Vec<String>,
+    pub features: Vec<Vec<f64>>,
+    pub target_name: Option<String>,
+    pub target: Vec<f64>,
+    pub missing: MissingValueReport,
+    /// The names behind the codes of every categorical feature, empty for
+    /// numeric ones: `categories[j][c]` is stored as `c` in column `j`. The
+    /// categories given in `CsvOptions` come first, then the others found
+    /// in the file, in sorted order. An encoder such as
+    /// `preprocessing::OneHotEncoder` turns the codes into model inputs.
+    pub categories: Vec<Vec<String>>,
+}
+
+impl Dataset {
+    pub fn from_csv_path(
+        path: impl AsRef<Path>,
+        options: &CsvOptions,
+    ) -> Result<Self, DatasetError> {
+        let table = CsvTable::from_path(path, options.delimiter, options.has_header)?;
+        Self::from_table(&table, options)
+    }
+
+    pub fn from_csv_reader<R: BufRead>(
+        reader: R,
+        options: &CsvOptions,
+    ) -> Result<Self, DatasetError> {
+        let table = CsvTable::from_reader(reader, options.delimiter, options.has_header)?;
+        Self::from_table(&table, options)
+    }
+
+    /// Parses the selected columns of an already-read table as numbers.
+    pub fn from_table(table: &CsvTable, options: &CsvOptions) -> Result<Self, DatasetError> {
+        let target_index = options
+            .target
+            .as_ref()
+            .map(|c| table.column_index(c))
+            .transpose()?;
+        let feature_indices: Vec<usize> = match &options.features {
+            Some(columns) => columns
+                .iter()
+                .map(|c| table.column_index(c))
+                .collect::<Result<_, _>>()?,
+            None => (0..table.n_columns())
+                .filter(|&j| Some(j) != target_index)
+                .collect(),
+        };
+        if let Some(j) = target_index.filter(|j| feature_indices.contains(j)) {
+            return Err(DatasetError::TargetIsFeature(table.headers[j].clone()));
+        }
+
+        // Target goes last so one pass parses every selected column
+        let selected: Vec<usize> = feature_indices
+            .iter()
+            .copied()
+            .chain(target_index)
+            .collect();
+        let categories = find_categories(table, options, &feature_indices)?;
+        let codes: Vec<HashMap<&str, usize>> = categories
+            .iter()
+            .map(|names| {
+                names
+                    .iter()
+                    .enumerate()
+                    .map(|(code, name)| (name.as_str(), code))
+                    .collect()
+            })
+            .collect();
+        let mut missing_counts = vec![0; selected.len()];
+        let mut first_missing_line = vec![0; selected.len()];
+        let mut report = MissingValueReport::default();
+
+        let mut features = Vec::with_capacity(table.records.len());
+        let mut target = Vec::with_capacity(table.records.len());
+
+        for (record, &line) in table.records.iter().zip(table.lines.iter()) {
+            let mut values = Vec::with_capacity(selected.len());
+            let mut row_has_missing = false;
+            let mut target_missing = false;
+
+            for (k, &j) in selected.iter().enumerate() {
+                let field = &record[j];
+                if options.is_missing(field) {
+                    if missing_counts[k] == 0 {
+                        first_missing_line[k] = line;
+                    }
+                    missing_counts[k] += 1;
+                    row_has_missing = true;
+                    target_missing |= target_index.is_some() && k == feature_indices.len();
+                    values.push(f64::NAN);
+                    continue;
+                }
+
+                if let Some(code) = codes.get(k).and_then(|codes| codes.get(field.trim())) {
+                    values.push(*code as f64);
+                    continue;
+                }
+                let value = field
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| DatasetError::Parse {
+                        line,
+                        column: table.headers[j].clone(),
+                        value: field.clone(),
+                    })?;
+                values.push(value);
+            }
+
+            if row_has_missing {
+                report.lines.push(line);
+                if options.missing == MissingValues::DropRows || target_missing {
+                    report.dropped_rows += 1;
+                    continue;
+                }
+            }
+
+            if target_index.is_some() {
+                target.push(values.pop().unwrap_or(f64::NAN));
+            }
+            features.push(values);
+        }
+
+        report.per_column = selected
+            .iter()
+            .zip(missing_counts.iter())
+            .map(|(&j, &count)| (table.headers[j].clone(), count))
+            .collect();
+
+        if options.missing == MissingValues::Error
+            && let Some(k) = missing_counts.iter().position(|&count| count > 0)
+        {
+            return Err(DatasetError::MissingValues {
+                column: table.headers[selected[k]].clone(),
+                count: missing_counts[k],
+                first_line: first_missing_line[k],
+            });
+        }
+        if features.is_empty() {
+            return Err(DatasetError::Empty);
+        }
+
+        Ok(Self {
+            feature_names: feature_indices
+                .iter()
+                .map(|&j| table.headers[j].clone())
+                .collect(),
+            features,
+            target_name: target_index.map(|j| table.headers[j].clone()),
+            target,
+            missing: report,
+            categories,
+        })
+    }
+
+    pub fn n_samples(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.feature_names.len()
+    }
+
+    /// Values of feature `j` across all samples.
+    pub fn feature_column(&self, j: usize) -> Vec<f64> {
+        self.features.iter().map(|row| row[j]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUMERIC: &str = "a,b,y\n1,2,3\n4,5,6\n7,8,9\n";
+    const CSV: &str = "x,color,y\n1,red,2\n,blue,3\n2,NA,\n3,red,6\n";
+
+    fn read(text: &str, options: &CsvOptions) -> Result<Dataset, DatasetError> {
+        Dataset::from_csv_reader(text.as_bytes(), options)
+    }
+
+    fn load(options: &CsvOptions) -> Result<Dataset, DatasetError> {
+        read(CSV, options)
+    }
+
+    #[test]
+    fn header_is_detected_from_the_first_record() {
+        let table = CsvTable::from_reader(NUMERIC.as_bytes(), ',', None).unwrap();
+        assert_eq!(table.headers, ["a", "b", "y"]);
+        assert_eq!(table.records.len(), 3);
+        assert_eq!(table.lines, [2, 3, 4]);
+
+        let headerless = "1,2,3\n4,5,6\n";
+        let table = CsvTable::from_reader(headerless.as_bytes(), ',', None).unwrap();
+        assert_eq!(table.headers, ["column_0", "column_1", "column_2"]);
+        assert_eq!(table.records.len(), 2);
+
+        // Forcing it overrides the guess either way
+        let table = CsvTable::from_reader(headerless.as_bytes(), ',', Some(true)).unwrap();
+        assert_eq!(table.headers, ["1", "2", "3"]);
+        let table = CsvTable::from_reader(NUMERIC.as_bytes(), ',', Some(false)).unwrap();
+        assert_eq!(table.records.len(), 4);
+    }
+
+    #[test]
+    fn quotes_delimiters_and_blank_lines() {
+        let text = "name;\"note\"\n\"a;b\";\"say \"\"hi\"\"\"\n\n\"multi\nline\";x\n";
+        let table = CsvTable::from_reader(text.as_bytes(), ';', None).unwrap();
+        assert_eq!(table.headers, ["name", "note"]);
+        assert_eq!(table.records[0], ["a;b", "say \"hi\""]);
+        assert_eq!(table.records[1], ["multi\nline", "x"]);
+        assert_eq!(table.lines, [2, 4]);
+
+        assert!(matches!(
+            CsvTable::from_reader("a,b\n\"1,2\n".as_bytes(), ',', None),
+            Err(DatasetError::UnterminatedQuote { line: 2 })
+        ));
+        assert!(matches!(
+            CsvTable::from_reader("a,b\n1,2\n3\n".as_bytes(), ',', None),
+            Err(DatasetError::RaggedRow {
+                line: 3,
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            CsvTable::from_reader("\n\n".as_bytes(), ',', None),
+            Err(DatasetError::Empty)
+        ));
+    }
+
+    #[test]
+    fn target_defaults_to_all_other_columns_as_features() {
+        let data = read(NUMERIC, &CsvOptions::new("b")).unwrap();
+        assert_eq!(data.feature_names, ["a", "y"]);
+        assert_eq!(data.target_name.as_deref(), Some("b"));
+        assert_eq!(data.target, [2.0, 5.0, 8.0]);
+        assert_eq!(data.features[1], [4.0, 6.0]);
+        assert!(data.missing.is_empty());
+
+        let data = read(NUMERIC, &CsvOptions::without_target()).unwrap();
+        assert_eq!(data.n_features(), 3);
+        assert_eq!(data.target_name, None);
+        assert!(data.target.is_empty());
+    }
+
+    #[test]
+    fn columns_are_selected_by_name_or_index() {
+        let by_name = read(NUMERIC, &CsvOptions::new("y").features(["b", "a"])).unwrap();
+        assert_eq!(by_name.feature_names, ["b", "a"]);
+        assert_eq!(by_name.features[0], [2.0, 1.0]);
+
+        let by_index = read(NUMERIC, &CsvOptions::new(2).features([1, 0])).unwrap();
+        assert_eq!(by_index, by_name);
+
+        assert!(matches!(
+            read(NUMERIC, &CsvOptions::new("z")),
+            Err(DatasetError::UnknownColumn(name)) if name == "z"
+        ));
+        assert!(matches!(
+            read(NUMERIC, &CsvOptions::new("y").features([3])),
+            Err(DatasetError::ColumnOutOfRange {
+                index: 3,
+                n_columns: 3
+            })
+        ));
+        assert!(matches!(
+            read(NUMERIC, &CsvOptions::new("y").features(["a", "y"])),
+            Err(DatasetError::TargetIsFeature(name)) if name == "y"
+        ));
+    }
+
+    #[test]
+    fn unparsable_fields_name_the_line_and_column() {
+        assert!(matches!(
+            read("a,y\n1,2\nx,3\n", &CsvOptions::new("y")),
+            Err(DatasetError::Parse { line: 3, column, value }) if column == "a" && value == "x"
+        ));
+    }
+
+    #[test]
+    fn missing_values_fail_by_default() {
+        assert!(matches!(
+            load(&CsvOptions::new("y").categorical("color")),
+            Err(DatasetError::MissingValues {
+                column,
+                count: 1,
+                first_line: 3
+            }) if column == "x"
+        ));
+    }
+
+    #[test]
+    fn dropped_rows_are_reported_per_column() {
+        let text = "a,b,y\n1,,3\nNA,5,6\n7,8,9\n?,n/a,null\n";
+        let data = read(text, &CsvOptions::new("y").missing(MissingValues::DropRows)).unwrap();
+        assert_eq!(data.features, [[7.0, 8.0]]);
+        assert_eq!(data.target, [9.0]);
+        let report = &data.missing;
+        assert_eq!(
+            report.per_column,
+            [
+                ("a".to_string(), 2),
+                ("b".to_string(), 2),
+                ("y".to_string(), 1)
+            ]
+        );
+        assert_eq!(report.total(), 5);
+        assert_eq!(report.lines, [2, 3, 5]);
+        assert_eq!(report.dropped_rows, 3);
+
+        assert!(matches!(
+            read(
+                "a,y\nNA,1\n",
+                &CsvOptions::new("y").missing(MissingValues::DropRows)
+            ),
+            Err(DatasetError::Empty)
+        ));
+    }
+
+    #[test]
+    fn keep_stores_missing_features_but_drops_missing_targets() {
+        let options = CsvOptions::new("y")
+            .categorical("color")
+            .missing(MissingValues::Keep);
+        let data = load(&options).unwrap();
+        assert_eq!(data.target, [2.0, 3.0, 6.0]);
+        assert_eq!(data.features.len(), 3);
+        assert!(data.features[1][0].is_nan());
+        assert_eq!(data.missing.dropped_rows, 1);
+        assert_eq!(data.missing.lines, [3, 4]);
+    }
+
+    #[test]
+    fn categories_keep_known_names_first() {
+        let options = CsvOptions::new("y")
+            .categories("color", vec!["red".to_string()])
+            .missing(MissingValues::DropRows);
+        let data = load(&options).unwrap();
+        assert_eq!(data.categories[1], ["red", "blue"]);
+        assert!(data.categories[0].is_empty());
+        assert_eq!(data.feature_column(1), [0.0, 0.0]);
+    }
+}
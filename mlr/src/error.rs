@@ -0,0 +1,471 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Failure of a linear algebra operation.
+///
+/// Returned by `matrix_functions`, `vectors_functions`,
+/// `matrix_with_vector_functions` and the `Matrix`/`Vector` types, so callers
+/// can match on the kind of failure instead of parsing a message. The models
+/// built on them return it from prediction, and wrap it in [`ModelError`]
+/// when fitting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinalgError {
+    /// A length didn't match what the operation needs, e.g. a vector whose
+    /// length differs from the matrix column count.
+    DimensionMismatch { expected: usize, found: usize },
+    /// Two matrices that must have identical `(rows, cols)` don't.
+    ShapeMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    /// The operation is only defined for square matrices.
+    NotSquare { rows: usize, cols: usize },
+    /// The matrix is singular to working precision.
+    Singular,
+    /// The input has no elements.
+    Empty,
+    /// Row `row` of a nested `&[Vec<f64>]` matrix has a different length
+    /// than the first row.
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The zero vector has no direction to normalize to.
+    ZeroNorm,
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "dimension mismatch: expected {}, found {}",
+                    expected, found
+                )
+            }
+            LinalgError::ShapeMismatch { left, right } => write!(
+                f,
+                "matrix shapes differ: {}x{} vs {}x{}",
+                left.0, left.1, right.0, right.1
+            ),
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "matrix must be square, got {}x{}", rows, cols)
+            }
+            LinalgError::Singular => write!(f, "matrix is singular"),
+            LinalgError::Empty => write!(f, "input is empty"),
+            LinalgError::RaggedRows {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} columns but the first row has {}",
+                row, found, expected
+            ),
+            LinalgError::ZeroNorm => write!(f, "cannot normalize a zero-length vector"),
+        }
+    }
+}
+
+impl Error for LinalgError {}
+
+/// Failure of a `preprocessing::Transformer`.
+///
+/// Input of the wrong shape comes through as [`LinalgError`]; the other
+/// variants are specific to fitting or applying a transformation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreprocessingError {
+    Linalg(LinalgError),
+    /// The transformation discards information, so it can't be undone.
+    NotInvertible,
+    /// A range whose lower end isn't below its upper end, or that reaches
+    /// outside the values it may take.
+    InvalidRange {
+        low: f64,
+        high: f64,
+    },
+    /// Column `column` holds a category the encoder wasn't fitted on.
+    UnknownCategory {
+        column: usize,
+        value: f64,
+    },
+    /// Column `column` is listed more than once among an encoder's columns.
+    DuplicateColumn {
+        column: usize,
+    },
+}
+
+impl fmt::Display for PreprocessingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreprocessingError::Linalg(e) => write!(f, "{}", e),
+            PreprocessingError::NotInvertible => write!(f, "transformation has no inverse"),
+            PreprocessingError::InvalidRange { low, high } => {
+                write!(f, "range [{}, {}] is empty or out of bounds", low, high)
+            }
+            PreprocessingError::UnknownCategory { column, value } => write!(
+                f,
+                "column {} has category {} that was not seen during fitting",
+                column, value
+            ),
+            PreprocessingError::DuplicateColumn { column } => {
+                write!(f, "column {} is listed more than once", column)
+            }
+        }
+    }
+}
+
+impl Error for PreprocessingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PreprocessingError::Linalg(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<LinalgError> for PreprocessingError {
+    fn from(e: LinalgError) -> Self {
+        PreprocessingError::Linalg(e)
+    }
+}
+
+/// Failure while fitting a model or predicting with it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelError {
+    /// The training data has the wrong shape, or a linear algebra step of
+    /// the fit failed.
+    Linalg(LinalgError),
+    /// A step of a `Pipeline` failed to fit or apply.
+    Preprocessing(PreprocessingError),
+    /// A class label that isn't below the classifier's number of classes.
+    LabelOutOfRange { label: usize, n_classes: usize },
+    /// A penalty strength that is negative, infinite or NaN.
+    InvalidAlpha(f64),
+    /// An elastic-net `l1_ratio` outside `[0, 1]`.
+    InvalidL1Ratio(f64),
+}
+
+impl fmt::Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelError::Linalg(e) => write!(f, "{}", e),
+            ModelError::Preprocessing(e) => write!(f, "{}", e),
+            ModelError::LabelOutOfRange { label, n_classes } => write!(
+                f,
+                "class label {} is out of range for {} classes",
+                label, n_classes
+            ),
+            ModelError::InvalidAlpha(alpha) => write!(
+                f,
+                "penalty strength must be a non-negative number, got {}",
+                alpha
+            ),
+            ModelError::InvalidL1Ratio(l1_ratio) => {
+                write!(f, "l1_ratio must be between 0 and 1, got {}", l1_ratio)
+            }
+        }
+    }
+}
+
+impl Error for ModelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ModelError::Linalg(e) => Some(e),
+            ModelError::Preprocessing(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<LinalgError> for ModelError {
+    fn from(e: LinalgError) -> Self {
+        ModelError::Linalg(e)
+    }
+}
+
+impl From<PreprocessingError> for ModelError {
+    fn from(e: PreprocessingError) -> Self {
+        ModelError::Preprocessing(e)
+    }
+}
+
+/// Failure while loading a dataset from CSV.
+#[derive(Debug)]
+pub enum DatasetError {
+    Io(io::Error),
+    /// The input contains no data rows.
+    Empty,
+    /// A quoted field was still open at the end of the input.
+    UnterminatedQuote {
+        line: usize,
+    },
+    /// A record has a different number of fields than the header.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// No column has this name.
+    UnknownColumn(String),
+    /// A column index past the last column.
+    ColumnOutOfRange {
+        index: usize,
+        n_columns: usize,
+    },
+    /// A field that is neither a number nor a missing-value marker.
+    Parse {
+        line: usize,
+        column: String,
+        value: String,
+    },
+    /// Missing values were found while the loader was set to reject them.
+    MissingValues {
+        column: String,
+        count: usize,
+        first_line: usize,
+    },
+    /// The target column is also listed as a feature.
+    TargetIsFeature(String),
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatasetError::Io(e) => write!(f, "I/O error: {}", e),
+            DatasetError::Empty => write!(f, "dataset has no data rows"),
+            DatasetError::UnterminatedQuote { line } => {
+                write!(f, "line {}: quoted field is never closed", line)
+            }
+            DatasetError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} fields, found {}",
+                line, expected, found
+            ),
+            DatasetError::UnknownColumn(name) => write!(f, "no column named '{}'", name),
+            DatasetError::ColumnOutOfRange { index, n_columns } => write!(
+                f,
+                "column index {} is out of range for {} columns",
+                index, n_columns
+            ),
+            DatasetError::Parse {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}: value '{}' in column '{}' is not a number",
+                line, value, column
+            ),
+            DatasetError::MissingValues {
+                column,
+                count,
+                first_line,
+            } => write!(
+                f,
+                "column '{}' has {} missing values (first on line {})",
+                column, count, first_line
+            ),
+            DatasetError::TargetIsFeature(name) => {
+                write!(f, "column '{}' is both the target and a feature", name)
+            }
+        }
+    }
+}
+
+impl Error for DatasetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DatasetError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DatasetError {
+    fn from(e: io::Error) -> Self {
+        DatasetError::Io(e)
+    }
+}
+
+/// Failure while saving or loading a model file.
+#[derive(Debug)]
+pub enum PersistError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+    /// A binary file that doesn't start with `persist::BINARY_MAGIC`.
+    BadMagic,
+    /// The file was written by a newer, incompatible version of the format.
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Io(e) => write!(f, "I/O error: {}", e),
+            PersistError::Json(e) => write!(f, "invalid JSON model file: {}", e),
+            PersistError::Binary(e) => write!(f, "invalid binary model file: {}", e),
+            PersistError::BadMagic => write!(f, "not an mlr model file"),
+            PersistError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "model file format version {} is newer than the supported version {}",
+                found, supported
+            ),
+        }
+    }
+}
+
+impl Error for PersistError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PersistError::Io(e) => Some(e),
+            PersistError::Json(e) => Some(e),
+            PersistError::Binary(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(e: io::Error) -> Self {
+        PersistError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(e: serde_json::Error) -> Self {
+        PersistError::Json(e)
+    }
+}
+
+impl From<bincode::Error> for PersistError {
+    fn from(e: bincode::Error) -> Self {
+        PersistError::Binary(e)
+    }
+}
+
+/// Invalid input to a metric.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricsError {
+    /// `y_true` and `y_pred` have different lengths.
+    LengthMismatch { y_true: usize, y_pred: usize },
+    /// There are no samples to score.
+    Empty,
+    /// Adjusted R² needs more samples than `n_features + 1`.
+    TooFewSamples { n_samples: usize, n_features: usize },
+    /// A class label outside `0..n_classes`.
+    LabelOutOfRange { label: usize, n_classes: usize },
+    /// The metric needs samples of both classes, e.g. a ROC curve.
+    SingleClass,
+}
+
+impl fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricsError::LengthMismatch { y_true, y_pred } => {
+                write!(f, "got {} true values but {} predictions", y_true, y_pred)
+            }
+            MetricsError::Empty => write!(f, "no samples to score"),
+            MetricsError::TooFewSamples {
+                n_samples,
+                n_features,
+            } => write!(
+                f,
+                "{} samples are too few for a model with {} features",
+                n_samples, n_features
+            ),
+            MetricsError::LabelOutOfRange { label, n_classes } => write!(
+                f,
+                "class label {} is out of range for {} classes",
+                label, n_classes
+            ),
+            MetricsError::SingleClass => {
+                write!(f, "the true labels contain only one class")
+            }
+        }
+    }
+}
+
+impl Error for MetricsError {}
+
+/// Failure while splitting data or cross-validating a model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelSelectionError {
+    /// Fitting or applying the model on a fold failed.
+    Model(ModelError),
+    /// Scoring a fold's predictions failed.
+    Metrics(MetricsError),
+    /// The feature rows and targets have different lengths.
+    LengthMismatch { rows: usize, targets: usize },
+    /// A split needs at least two samples, and `k`-fold at least `k`.
+    TooFewSamples { n_samples: usize, n_splits: usize },
+    /// `k`-fold needs `k >= 2`.
+    TooFewSplits(usize),
+    /// A test fraction outside `(0, 1)`.
+    InvalidFraction(f64),
+}
+
+impl fmt::Display for ModelSelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelSelectionError::Model(e) => write!(f, "model error: {}", e),
+            ModelSelectionError::Metrics(e) => write!(f, "scoring error: {}", e),
+            ModelSelectionError::LengthMismatch { rows, targets } => {
+                write!(f, "got {} feature rows but {} targets", rows, targets)
+            }
+            ModelSelectionError::TooFewSamples {
+                n_samples,
+                n_splits,
+            } => write!(
+                f,
+                "cannot split {} samples into {} parts",
+                n_samples, n_splits
+            ),
+            ModelSelectionError::TooFewSplits(k) => {
+                write!(f, "cross-validation needs at least 2 folds, got {}", k)
+            }
+            ModelSelectionError::InvalidFraction(fraction) => {
+                write!(f, "test fraction {} is not between 0 and 1", fraction)
+            }
+        }
+    }
+}
+
+impl Error for ModelSelectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ModelSelectionError::Model(e) => Some(e),
+            ModelSelectionError::Metrics(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ModelError> for ModelSelectionError {
+    fn from(e: ModelError) -> Self {
+        ModelSelectionError::Model(e)
+    }
+}
+
+impl From<LinalgError> for ModelSelectionError {
+    fn from(e: LinalgError) -> Self {
+        ModelSelectionError::Model(e.into())
+    }
+}
+
+impl From<MetricsError> for ModelSelectionError {
+    fn from(e: MetricsError) -> Self {
+        ModelSelectionError::Metrics(e)
+    }
+}
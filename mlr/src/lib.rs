@@ -0,0 +1,34 @@
+//! Linear regression, linear classifiers and the linear algebra they are built on.
+//!
+//! - [`linalg`]: `Matrix`/`Vector` types and the slice-based matrix and vector routines
+//! - [`models`]: single- and multi-variable linear and polynomial regression, logistic and
+//!   softmax classifiers, and pipelines that preprocess their inputs
+//! - [`metrics`]: scoring predictions against true values
+//! - [`model_selection`]: train/test splits and cross-validation
+//! - [`optim`] and [`training`]: optimizers and the gradient-descent loop
+//! - [`regularization`]: ridge, lasso and elastic-net penalties
+//! - [`preprocessing`]: feature transformations: polynomial expansion, scaling, category
+//!   encoding and missing-value imputation
+//! - [`persist`]: saving and loading trained models (JSON or binary)
+//! - [`datagen`]: synthetic datasets
+//! - [`random`]: seeding for reproducible runs
+//! - [`dataset`]: loading training data, numeric or categorical, from CSV
+//! - [`visualization`]: PNG plots of model output
+//!
+//! [`prelude`] re-exports the types most programs need.
+
+pub mod datagen;
+pub mod dataset;
+pub mod error;
+pub mod linalg;
+pub mod metrics;
+pub mod model_selection;
+pub mod models;
+pub mod optim;
+pub mod persist;
+pub mod prelude;
+pub mod preprocessing;
+pub mod random;
+pub mod regularization;
+pub mod training;
+pub mod visualization;
@@ -0,0 +1,427 @@
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use crate::error::LinalgError;
+use crate::linalg::matrix_functions::{LuDecomposition, matrix_lu_decompose, matrix_shape};
+
+/// Dense matrix stored contiguously in row-major order.
+///
+/// Unlike `&[Vec<f64>]`, a `Matrix` can't be ragged: every constructor checks
+/// the shape once, so operations only need to compare `shape()`s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { 1.0 } else { 0.0 })
+    }
+
+    /// Builds a matrix whose entry `(i, j)` is `f(i, j)`.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> f64) -> Self {
+        let mut data = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(f(i, j));
+            }
+        }
+
+        Self { rows, cols, data }
+    }
+
+    /// Wraps row-major `data` as a `rows × cols` matrix.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<f64>) -> Result<Self, LinalgError> {
+        if data.len() != rows * cols {
+            return Err(LinalgError::DimensionMismatch {
+                expected: rows * cols,
+                found: data.len(),
+            });
+        }
+
+        Ok(Self { rows, cols, data })
+    }
+
+    /// Copies nested rows into a matrix, rejecting ragged input.
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self, LinalgError> {
+        let (n_rows, cols) = matrix_shape(rows)?;
+
+        Ok(Self {
+            rows: n_rows,
+            cols,
+            data: rows.iter().flatten().copied().collect(),
+        })
+    }
+
+    /// Single-column matrix holding `values`.
+    pub fn column_vector(values: &[f64]) -> Self {
+        Self {
+            rows: values.len(),
+            cols: 1,
+            data: values.to_vec(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<f64> {
+        self.data
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [f64] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[f64]> {
+        // `chunks_exact(0)` panics, and a zero-width matrix still has rows
+        (0..self.rows).map(move |i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> Vec<f64> {
+        (0..self.rows).map(|i| self[(i, j)]).collect()
+    }
+
+    /// Copies the matrix back into the nested layout used by `matrix_functions`.
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        self.iter_rows().map(|row| row.to_vec()).collect()
+    }
+
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|&val| f(val)).collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)])
+    }
+
+    /// Matrix product, or an error when `self.cols() != other.rows()`.
+    pub fn dot(&self, other: &Matrix) -> Result<Matrix, LinalgError> {
+        if self.cols != other.rows {
+            return Err(LinalgError::DimensionMismatch {
+                expected: self.cols,
+                found: other.rows,
+            });
+        }
+
+        let mut result = Matrix::zeros(self.rows, other.cols);
+
+        // i-k-j order walks both `other` and `result` along contiguous rows
+        for i in 0..self.rows {
+            let out_row = result.row_mut(i);
+            for (k, &a) in self.row(i).iter().enumerate() {
+                for (out, &b) in out_row.iter_mut().zip(other.row(k)) {
+                    *out += a * b;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Element-wise product, or an error when the shapes differ.
+    pub fn hadamard(&self, other: &Matrix) -> Result<Matrix, LinalgError> {
+        self.zip_with(other, |a, b| a * b)
+    }
+
+    pub fn lu(&self) -> Result<LuDecomposition, LinalgError> {
+        matrix_lu_decompose(&self.to_rows())
+    }
+
+    pub fn determinant(&self) -> Result<f64, LinalgError> {
+        Ok(self.lu()?.determinant())
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, LinalgError> {
+        let lu = self.lu()?;
+        if lu.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+
+        Matrix::from_rows(&lu.inverse()?)
+    }
+
+    /// Solves `self · x = b` for a square `self`.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+        self.lu()?.solve(b)
+    }
+
+    fn zip_with(&self, other: &Matrix, f: impl Fn(f64, f64) -> f64) -> Result<Matrix, LinalgError> {
+        if self.shape() != other.shape() {
+            return Err(LinalgError::ShapeMismatch {
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+
+        Ok(Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        })
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        assert!(i < self.rows && j < self.cols, "Matrix index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        assert!(i < self.rows && j < self.cols, "Matrix index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            let cells: Vec<String> = row.iter().map(|val| format!("{:.4}", val)).collect();
+            writeln!(f, "[{}]", cells.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// The operators panic on shape mismatch, like slice indexing does; use
+// `dot`/`hadamard` or the `matrix_functions` API for a `Result` instead.
+macro_rules! impl_matrix_op {
+    ($op:ident, $method:ident, $body:expr) => {
+        impl $op<&Matrix> for &Matrix {
+            type Output = Matrix;
+
+            fn $method(self, rhs: &Matrix) -> Matrix {
+                let f: fn(&Matrix, &Matrix) -> Result<Matrix, LinalgError> = $body;
+                f(self, rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl $op<Matrix> for Matrix {
+            type Output = Matrix;
+
+            fn $method(self, rhs: Matrix) -> Matrix {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $op<&Matrix> for Matrix {
+            type Output = Matrix;
+
+            fn $method(self, rhs: &Matrix) -> Matrix {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $op<Matrix> for &Matrix {
+            type Output = Matrix;
+
+            fn $method(self, rhs: Matrix) -> Matrix {
+                self.$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_matrix_op!(Add, add, |a, b| a.zip_with(b, |x, y| x + y));
+impl_matrix_op!(Sub, sub, |a, b| a.zip_with(b, |x, y| x - y));
+impl_matrix_op!(Mul, mul, |a, b| a.dot(b));
+
+impl Mul<f64> for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, scalar: f64) -> Matrix {
+        self.map(|val| val * scalar)
+    }
+}
+
+impl Mul<f64> for Matrix {
+    type Output = Matrix;
+
+    fn mul(self, scalar: f64) -> Matrix {
+        &self * scalar
+    }
+}
+
+impl Mul<&Matrix> for f64 {
+    type Output = Matrix;
+
+    fn mul(self, matrix: &Matrix) -> Matrix {
+        matrix * self
+    }
+}
+
+impl Mul<Matrix> for f64 {
+    type Output = Matrix;
+
+    fn mul(self, matrix: Matrix) -> Matrix {
+        &matrix * self
+    }
+}
+
+impl Neg for &Matrix {
+    type Output = Matrix;
+
+    fn neg(self) -> Matrix {
+        self.map(|val| -val)
+    }
+}
+
+impl Neg for Matrix {
+    type Output = Matrix;
+
+    fn neg(self) -> Matrix {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(rows: &[&[f64]]) -> Matrix {
+        Matrix::from_rows(&rows.iter().map(|row| row.to_vec()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn construction_checks_the_shape() {
+        assert_eq!(
+            Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0]]),
+            Err(LinalgError::RaggedRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Matrix::from_vec(2, 3, vec![1.0; 5]),
+            Err(LinalgError::DimensionMismatch {
+                expected: 6,
+                found: 5
+            })
+        );
+
+        let empty = Matrix::from_rows(&[]).unwrap();
+        assert_eq!(empty.shape(), (0, 0));
+        assert!(empty.is_empty());
+        assert_eq!(empty.determinant(), Err(LinalgError::Empty));
+
+        let a = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(a, m(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]));
+        assert_eq!(a[(1, 0)], 4.0);
+        assert_eq!(a.column(2), [3.0, 6.0]);
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let a = m(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let t = a.transpose();
+        assert_eq!(t, m(&[&[1.0, 4.0], &[2.0, 5.0], &[3.0, 6.0]]));
+        assert_eq!(t.transpose(), a);
+    }
+
+    #[test]
+    fn dot_multiplies_compatible_shapes() {
+        let a = m(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let b = m(&[&[7.0, 8.0], &[9.0, 10.0], &[11.0, 12.0]]);
+        let expected = m(&[&[58.0, 64.0], &[139.0, 154.0]]);
+        assert_eq!(a.dot(&b).unwrap(), expected);
+        assert_eq!(&a * &b, expected);
+        assert_eq!(a.dot(&Matrix::identity(3)).unwrap(), a);
+    }
+
+    #[test]
+    fn shape_mismatches_are_errors() {
+        let a = m(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        assert_eq!(
+            a.dot(&a),
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            a.hadamard(&a.transpose()),
+            Err(LinalgError::ShapeMismatch {
+                left: (2, 3),
+                right: (3, 2)
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn operators_panic_on_shape_mismatch() {
+        let a = Matrix::zeros(2, 3);
+        let _ = &a + &a.transpose();
+    }
+
+    #[test]
+    fn inverse_and_solve_go_through_lu() {
+        let a = m(&[&[4.0, 7.0], &[2.0, 6.0]]);
+        assert!((a.determinant().unwrap() - 10.0).abs() < 1e-12);
+        let product = a.dot(&a.inverse().unwrap()).unwrap();
+        assert!(
+            (&product - &Matrix::identity(2))
+                .as_slice()
+                .iter()
+                .all(|v| v.abs() < 1e-12)
+        );
+        assert_eq!(
+            m(&[&[1.0, 2.0], &[2.0, 4.0]]).inverse(),
+            Err(LinalgError::Singular)
+        );
+        assert_eq!(
+            Matrix::zeros(2, 3).determinant(),
+            Err(LinalgError::NotSquare { rows: 2, cols: 3 })
+        );
+    }
+}
@@ -0,0 +1,499 @@
+use crate::error::LinalgError;
+use crate::linalg::matrix::Matrix;
+
+/// `(rows, cols)` of a nested matrix, or `RaggedRows` if the rows differ in length.
+pub fn matrix_shape(mat: &[Vec<f64>]) -> Result<(usize, usize), LinalgError> {
+    let cols = mat.first().map_or(0, |row| row.len());
+
+    match mat.iter().position(|row| row.len() != cols) {
+        Some(row) => Err(LinalgError::RaggedRows {
+            row,
+            expected: cols,
+            found: mat[row].len(),
+        }),
+        None => Ok((mat.len(), cols)),
+    }
+}
+
+fn check_same_shape(mat1: &[Vec<f64>], mat2: &[Vec<f64>]) -> Result<(), LinalgError> {
+    let left = matrix_shape(mat1)?;
+    let right = matrix_shape(mat2)?;
+
+    if left != right {
+        return Err(LinalgError::ShapeMismatch { left, right });
+    }
+
+    Ok(())
+}
+
+fn check_square(matrix: &[Vec<f64>]) -> Result<(), LinalgError> {
+    let (rows, cols) = matrix_shape(matrix)?;
+
+    if rows != cols {
+        return Err(LinalgError::NotSquare { rows, cols });
+    }
+
+    Ok(())
+}
+
+pub fn matrix_add(mat1: &[Vec<f64>], mat2: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+    check_same_shape(mat1, mat2)?;
+
+    let result: Vec<Vec<f64>> = mat1
+        .iter()
+        .zip(mat2.iter())
+        .map(|(row1, row2)| row1.iter().zip(row2.iter()).map(|(a, b)| a + b).collect())
+        .collect();
+
+    Ok(result)
+}
+
+pub fn matrix_sub(mat1: &[Vec<f64>], mat2: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+    check_same_shape(mat1, mat2)?;
+
+    let result: Vec<Vec<f64>> = mat1
+        .iter()
+        .zip(mat2.iter())
+        .map(|(row1, row2)| row1.iter().zip(row2.iter()).map(|(a, b)| a - b).collect())
+        .collect();
+
+    Ok(result)
+}
+
+pub fn matrix_scalar(mat1: &[Vec<f64>], scalar: f64) -> Result<Vec<Vec<f64>>, LinalgError> {
+    // Ensure all rows have the same length (valid matrix check)
+    if matrix_shape(mat1)?.0 == 0 {
+        return Err(LinalgError::Empty);
+    }
+
+    // Perform scalar multiplication
+    let result: Vec<Vec<f64>> = mat1
+        .iter()
+        .map(|row| row.iter().map(|&a| a * scalar).collect()) // Multiply each element by scalar
+        .collect();
+
+    Ok(result)
+}
+
+pub fn matrix_hadamard_prod(mat1: &[Vec<f64>], mat2: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+    check_same_shape(mat1, mat2)?;
+
+    let result: Vec<Vec<f64>> = mat1
+        .iter()
+        .zip(mat2.iter())
+        .map(|(row1, row2)| row1.iter().zip(row2.iter()).map(|(a, b)| a * b).collect())
+        .collect();
+
+
+    Ok(result)
+}
+
+pub fn matrix_dot_prod(mat1: &[Vec<f64>], mat2: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+    if mat1.is_empty() || mat2.is_empty() {
+        return Err(LinalgError::Empty);
+    }
+    if mat1[0].len() != mat2.len() {
+        return Err(LinalgError::DimensionMismatch {
+            expected: mat1[0].len(),
+            found: mat2.len(),
+        });
+    }
+
+    let product = Matrix::from_rows(mat1)?.dot(&Matrix::from_rows(mat2)?)?;
+
+    Ok(product.to_rows())
+}
+
+pub fn matrix_transpose(mat1: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let num_rows = mat1.len();
+    let num_cols = mat1.first().map_or(0, |row| row.len());
+
+    let mut result: Vec<Vec<f64>> = vec![vec![0.0;num_rows];num_cols];
+
+    for (i, row) in mat1.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate() {
+            result[j][i] = val;
+        }
+    }
+
+    result
+}
+
+// Copy of `matrix` with row `row` and column `col` removed
+fn matrix_minor(matrix: &[Vec<f64>], row: usize, col: usize) -> Vec<Vec<f64>> {
+    matrix
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != row)
+        .map(|(_, r)| {
+            r.iter()
+                .enumerate()
+                .filter(|&(j, _)| j != col)
+                .map(|(_, &val)| val)
+                .collect()
+        })
+        .collect()
+}
+
+/// LU decomposition with partial pivoting, `P·A = L·U`.
+///
+/// `lu` stores both factors in one square matrix: `U` on and above the
+/// diagonal, and `L` strictly below it (its unit diagonal is implied).
+/// Row `i` of `P·A` is row `pivots[i]` of `A`.
+pub struct LuDecomposition {
+    pub lu: Vec<Vec<f64>>,
+    pub pivots: Vec<usize>,
+    pub sign: f64,
+}
+
+pub fn matrix_lu_decompose(matrix: &[Vec<f64>]) -> Result<LuDecomposition, LinalgError> {
+    let n = matrix.len();
+
+    if n == 0 {
+        return Err(LinalgError::Empty);
+    }
+    check_square(matrix)?;
+
+    let mut lu = matrix.to_vec();
+    let mut pivots: Vec<usize> = (0..n).collect();
+    let mut sign = 1.0;
+
+    for k in 0..n {
+        // Partial pivoting: bring the largest remaining entry of column k up
+        let pivot_row = (k..n)
+            .max_by(|&a, &b| lu[a][k].abs().total_cmp(&lu[b][k].abs()))
+            .unwrap_or(k);
+        if pivot_row != k {
+            lu.swap(k, pivot_row);
+            pivots.swap(k, pivot_row);
+            sign = -sign;
+        }
+
+        let pivot = lu[k][k];
+        if pivot == 0.0 {
+            continue; // Column already eliminated, the matrix is singular
+        }
+
+        let (upper, lower) = lu.split_at_mut(k + 1);
+        let pivot_vals = &upper[k];
+        for row in lower.iter_mut() {
+            let factor = row[k] / pivot;
+            row[k] = factor;
+            for (val, &p) in row.iter_mut().zip(pivot_vals.iter()).skip(k + 1) {
+                *val -= factor * p;
+            }
+        }
+    }
+
+    Ok(LuDecomposition { lu, pivots, sign })
+}
+
+impl LuDecomposition {
+    pub fn size(&self) -> usize {
+        self.lu.len()
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.sign * self.lu.iter().enumerate().map(|(i, row)| row[i]).product::<f64>()
+    }
+
+    /// True when some pivot is negligible relative to the largest entry of `U`,
+    /// i.e. the matrix is singular to working precision.
+    pub fn is_singular(&self) -> bool {
+        let n = self.size();
+        let scale = self
+            .lu
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| row[i..].iter())
+            .fold(0.0_f64, |acc, &val| acc.max(val.abs()));
+        let tolerance = scale * n as f64 * f64::EPSILON;
+
+        scale == 0.0 || self.lu.iter().enumerate().any(|(i, row)| row[i].abs() <= tolerance)
+    }
+
+    /// Solves `A·x = b` by forward substitution with `L` then back substitution with `U`.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+        let n = self.size();
+
+        if b.len() != n {
+            return Err(LinalgError::DimensionMismatch {
+                expected: n,
+                found: b.len(),
+            });
+        }
+        if self.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+
+        let mut x: Vec<f64> = self.pivots.iter().map(|&p| b[p]).collect();
+
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|j| self.lu[i][j] * x[j]).sum();
+            x[i] -= sum;
+        }
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|j| self.lu[i][j] * x[j]).sum();
+            x[i] = (x[i] - sum) / self.lu[i][i];
+        }
+
+        Ok(x)
+    }
+
+    pub fn inverse(&self) -> Result<Vec<Vec<f64>>, LinalgError> {
+        let n = self.size();
+        let mut columns = Vec::with_capacity(n);
+
+        for j in 0..n {
+            let mut unit = vec![0.0; n];
+            unit[j] = 1.0;
+            columns.push(self.solve(&unit)?);
+        }
+
+        // Each solve produced one column of the inverse
+        Ok(matrix_transpose(&columns))
+    }
+}
+
+/// Determinant via LU decomposition. Returns `NaN` for non-square input and
+/// `1.0` for the empty matrix.
+pub fn matrix_determinant(matrix: &[Vec<f64>]) -> f64 {
+    if matrix.is_empty() {
+        return 1.0;
+    }
+
+    match matrix_lu_decompose(matrix) {
+        Ok(lu) => lu.determinant(),
+        Err(_) => f64::NAN,
+    }
+}
+
+/// Solves the square system `matrix · x = b`.
+pub fn matrix_solve(matrix: &[Vec<f64>], b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+    matrix_lu_decompose(matrix)?.solve(b)
+}
+
+pub fn matrix_cofactor(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = matrix.len();
+    let mut cofactor_matrix = vec![vec![0.0;n];n];
+
+    for (i, cofactor_row) in cofactor_matrix.iter_mut().enumerate() {
+        for (j, cofactor) in cofactor_row.iter_mut().enumerate() {
+            let sub_matrix = matrix_minor(matrix, i, j);
+
+            let sign = if (i + j) % 2 == 0 {1.0} else {-1.0};
+            *cofactor = sign * matrix_determinant(&sub_matrix);
+        }
+    }
+
+    cofactor_matrix
+}
+
+pub fn matrix_adjugate(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+    check_square(matrix)?;
+
+    let cofactor = matrix_cofactor(matrix);
+    let adjugate_matrix = matrix_transpose(&cofactor);
+
+    Ok(adjugate_matrix)
+}
+
+pub fn matrix_inverse(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+    check_square(matrix)?;
+
+    let lu = matrix_lu_decompose(matrix)?;
+
+    if lu.is_singular() {
+        return Err(LinalgError::Singular);
+    }
+
+    lu.inverse()
+}
+pub fn test_matrix() {
+    // Test matrices for operations
+    let mat1 = vec![
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+    ];
+
+    let mat2 = vec![
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        vec![3.0, 4.0, 3.0, 6.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+    ];
+
+
+    println!("Testing matrix addition...");
+    match matrix_add(&mat1, &mat2) {
+        Ok(result) => println!("Matrix Addition Result: {:?}", result),
+        Err(e) => println!("Matrix Addition Error: {}", e),
+    }
+
+    println!("Testing matrix subtraction...");
+    match matrix_sub(&mat1, &mat2) {
+        Ok(result) => println!("Matrix Subtraction Result: {:?}", result),
+        Err(e) => println!("Matrix Subtraction Error: {}", e),
+    }
+
+    println!("Testing matrix scalar multiplication...");
+    let scalar = 2.0;
+    match matrix_scalar(&mat1, scalar) {
+        Ok(result) => println!("Matrix Scalar Multiplication Result: {:?}", result),
+        Err(e) => println!("Matrix Scalar Multiplication Error: {}", e),
+    }
+
+    println!("Testing matrix Hadamard product...");
+    match matrix_hadamard_prod(&mat1, &mat2) {
+        Ok(result) => println!("Matrix Hadamard Product Result: {:?}", result),
+        Err(e) => println!("Matrix Hadamard Product Error: {}", e),
+    }
+
+    println!("Testing matrix dot product...");
+    match matrix_dot_prod(&mat1, &mat2) {
+        Ok(result) => println!("Matrix Dot Product Result: {:?}", result),
+        Err(e) => println!("Matrix Dot Product Error: {}", e),
+    }
+
+    println!("Testing matrix transpose...");
+    let transposed = matrix_transpose(&mat1);
+    println!("Matrix Transpose Result: {:?}", transposed);
+
+    println!("Testing matrix determinant...");
+    let determinant = matrix_determinant(&mat1);
+    println!("Matrix Determinant Result: {}", determinant);
+
+    println!("Testing matrix cofactor...");
+    let cofactor = matrix_cofactor(&mat1);
+    println!("Matrix Cofactor Result: {:?}", cofactor);
+
+    println!("Testing matrix adjugate...");
+    match matrix_adjugate(&mat1) {
+        Ok(result) => println!("Matrix Adjugate Result: {:?}", result),
+        Err(e) => println!("Matrix Adjugate Error: {}", e),
+    }
+
+    println!("Testing matrix inverse...");
+    match matrix_inverse(&mat1) {
+        Ok(result) => println!("Matrix Inverse Result: {:?}", result),
+        Err(e) => println!("Matrix Inverse Error: {}", e),
+    }
+}
+
+pub fn test_vid() {
+    let matrix: Vec<Vec<f64>> = vec![
+        vec![11.0, 12.0],
+        vec![4.0, 8.0]
+    ];
+    match matrix_inverse(&matrix) {
+        Ok(result) => println!("Matrix inverse: {:?}", result),
+        Err(e) => println!("MAtrix inverse Error: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a() -> Vec<Vec<f64>> {
+        vec![
+            vec![2.0, 1.0, 1.0],
+            vec![4.0, -6.0, 0.0],
+            vec![-2.0, 7.0, 2.0],
+        ]
+    }
+
+    fn assert_close(found: &[f64], expected: &[f64]) {
+        assert_eq!(found.len(), expected.len());
+        for (f, e) in found.iter().zip(expected) {
+            assert!((f - e).abs() < 1e-12, "{:?} != {:?}", found, expected);
+        }
+    }
+
+    #[test]
+    fn determinant_of_a_known_matrix() {
+        assert!((matrix_determinant(&a()) + 16.0).abs() < 1e-12);
+        assert_eq!(matrix_determinant(&[]), 1.0);
+    }
+
+    #[test]
+    fn solve_recovers_a_known_solution() {
+        // a · [1, 2, 3]
+        let x = matrix_solve(&a(), &[7.0, -8.0, 18.0]).unwrap();
+        assert_close(&x, &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let inverse = matrix_inverse(&a()).unwrap();
+        let product = matrix_dot_prod(&a(), &inverse).unwrap();
+        for (i, row) in product.iter().enumerate() {
+            let unit: Vec<f64> = (0..3).map(|j| f64::from(u8::from(i == j))).collect();
+            assert_close(row, &unit);
+        }
+    }
+
+    #[test]
+    fn zero_leading_entry_needs_pivoting() {
+        let swap = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        let lu = matrix_lu_decompose(&swap).unwrap();
+        assert_eq!(lu.pivots, [1, 0]);
+        assert_eq!(lu.determinant(), -1.0);
+        assert_close(&matrix_solve(&swap, &[3.0, 5.0]).unwrap(), &[5.0, 3.0]);
+        assert_eq!(matrix_inverse(&swap).unwrap(), swap);
+    }
+
+    #[test]
+    fn singular_matrices_are_detected() {
+        let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert!(matrix_lu_decompose(&singular).unwrap().is_singular());
+        assert_eq!(matrix_determinant(&singular), 0.0);
+        assert_eq!(matrix_inverse(&singular), Err(LinalgError::Singular));
+        assert_eq!(
+            matrix_solve(&singular, &[1.0, 2.0]),
+            Err(LinalgError::Singular)
+        );
+        let zeros = vec![vec![0.0; 3]; 3];
+        assert_eq!(matrix_inverse(&zeros), Err(LinalgError::Singular));
+    }
+
+    #[test]
+    fn bad_shapes_are_rejected() {
+        let wide = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let not_square = LinalgError::NotSquare { rows: 2, cols: 3 };
+        assert_eq!(matrix_inverse(&wide), Err(not_square.clone()));
+        assert_eq!(matrix_solve(&wide, &[1.0, 2.0]), Err(not_square));
+        assert!(matrix_determinant(&wide).is_nan());
+
+        assert_eq!(matrix_inverse(&[]), Err(LinalgError::Empty));
+        assert_eq!(
+            matrix_inverse(&[vec![1.0, 2.0], vec![3.0]]),
+            Err(LinalgError::RaggedRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            matrix_solve(&a(), &[1.0, 2.0]),
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use crate::error::LinalgError;
+
+pub fn matrix_by_vector(matrix: &[Vec<f64>], vector: &[f64]) -> Result<Vec<f64>, LinalgError> {
+    if matrix.is_empty() {
+        return Err(LinalgError::Empty);
+    }
+    if matrix[0].len() != vector.len() {
+        return Err(LinalgError::DimensionMismatch {
+            expected: matrix[0].len(),
+            found: vector.len(),
+        });
+    }
+
+    let result: Vec<f64> = matrix
+        .iter()
+        .map(|row| row.iter().zip(vector).map(|(a, b)| a * b).sum())
+        .collect();
+
+    Ok(result)
+}
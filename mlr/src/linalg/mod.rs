@@ -0,0 +1,12 @@
+//! Dense linear algebra: the `Matrix` and `Vector` types, plus the original
+//! slice-based routines over `&[Vec<f64>]` and `&[f64]`.
+
+pub mod matrix;
+pub mod matrix_functions;
+pub mod matrix_with_vector_functions;
+pub mod vector;
+pub mod vectors_functions;
+
+pub use matrix::Matrix;
+pub use matrix_functions::LuDecomposition;
+pub use vector::Vector;
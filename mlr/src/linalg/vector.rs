@@ -0,0 +1,358 @@
+use std::fmt;
+use std::ops::{Add, Deref, DerefMut, Div, Mul, Neg, Sub};
+
+use crate::error::LinalgError;
+use crate::linalg::matrix::Matrix;
+use crate::linalg::vectors_functions::{vec_cross_prod, vec_dot_prod};
+
+/// Owned column vector of `f64`.
+///
+/// Derefs to `[f64]`, so a `&Vector` can be passed anywhere the slice-based
+/// `vectors_functions` or `matrix_by_vector` API is expected without copying.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Vector(Vec<f64>);
+
+impl Vector {
+    pub fn new(values: Vec<f64>) -> Self {
+        Self(values)
+    }
+
+    pub fn zeros(len: usize) -> Self {
+        Self(vec![0.0; len])
+    }
+
+    pub fn from_fn(len: usize, f: impl FnMut(usize) -> f64) -> Self {
+        Self((0..len).map(f).collect())
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<f64> {
+        self.0
+    }
+
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self(self.0.iter().map(|&val| f(val)).collect())
+    }
+
+    pub fn dot(&self, other: &Vector) -> Result<f64, LinalgError> {
+        vec_dot_prod(self, other)
+    }
+
+    pub fn cross(&self, other: &Vector) -> Result<Vector, LinalgError> {
+        vec_cross_prod(self, other).map(Vector)
+    }
+
+    /// Euclidean (L2) norm.
+    pub fn norm(&self) -> f64 {
+        self.0.iter().map(|val| val * val).sum::<f64>().sqrt()
+    }
+
+    /// Unit vector in the same direction, or an error for the zero vector.
+    pub fn normalize(&self) -> Result<Vector, LinalgError> {
+        let norm = self.norm();
+        if norm == 0.0 {
+            return Err(LinalgError::ZeroNorm);
+        }
+
+        Ok(self / norm)
+    }
+
+    /// Views the vector as an `n × 1` matrix.
+    pub fn to_column_matrix(&self) -> Matrix {
+        Matrix::column_vector(self)
+    }
+
+    fn zip_with(&self, other: &Vector, f: impl Fn(f64, f64) -> f64) -> Result<Vector, LinalgError> {
+        if self.len() != other.len() {
+            return Err(LinalgError::DimensionMismatch {
+                expected: self.len(),
+                found: other.len(),
+            });
+        }
+
+        Ok(Vector(
+            self.0
+                .iter()
+                .zip(other.0.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        ))
+    }
+}
+
+impl Matrix {
+    /// Matrix-vector product, or an error when `self.cols() != vector.len()`.
+    pub fn mul_vector(&self, vector: &[f64]) -> Result<Vector, LinalgError> {
+        if self.cols() != vector.len() {
+            return Err(LinalgError::DimensionMismatch {
+                expected: self.cols(),
+                found: vector.len(),
+            });
+        }
+
+        Ok(Vector(
+            self.iter_rows()
+                .map(|row| row.iter().zip(vector).map(|(a, b)| a * b).sum())
+                .collect(),
+        ))
+    }
+}
+
+impl Deref for Vector {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        &self.0
+    }
+}
+
+impl DerefMut for Vector {
+    fn deref_mut(&mut self) -> &mut [f64] {
+        &mut self.0
+    }
+}
+
+impl From<Vec<f64>> for Vector {
+    fn from(values: Vec<f64>) -> Self {
+        Self(values)
+    }
+}
+
+impl From<Vector> for Vec<f64> {
+    fn from(vector: Vector) -> Self {
+        vector.0
+    }
+}
+
+impl From<&[f64]> for Vector {
+    fn from(values: &[f64]) -> Self {
+        Self(values.to_vec())
+    }
+}
+
+impl FromIterator<f64> for Vector {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self.0.iter().map(|val| format!("{:.4}", val)).collect();
+        write!(f, "[{}]", cells.join(", "))
+    }
+}
+
+// Element-wise operators panic on length mismatch, like `Matrix`'s do; the
+// `vectors_functions` API returns a `Result` instead.
+macro_rules! impl_vector_op {
+    ($op:ident, $method:ident, $sym:tt) => {
+        impl $op<&Vector> for &Vector {
+            type Output = Vector;
+
+            fn $method(self, rhs: &Vector) -> Vector {
+                self.zip_with(rhs, |a, b| a $sym b)
+                    .unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl $op<Vector> for Vector {
+            type Output = Vector;
+
+            fn $method(self, rhs: Vector) -> Vector {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $op<&Vector> for Vector {
+            type Output = Vector;
+
+            fn $method(self, rhs: &Vector) -> Vector {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $op<Vector> for &Vector {
+            type Output = Vector;
+
+            fn $method(self, rhs: Vector) -> Vector {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $op<f64> for &Vector {
+            type Output = Vector;
+
+            fn $method(self, scalar: f64) -> Vector {
+                self.map(|val| val $sym scalar)
+            }
+        }
+
+        impl $op<f64> for Vector {
+            type Output = Vector;
+
+            fn $method(self, scalar: f64) -> Vector {
+                (&self).$method(scalar)
+            }
+        }
+    };
+}
+
+impl_vector_op!(Add, add, +);
+impl_vector_op!(Sub, sub, -);
+impl_vector_op!(Mul, mul, *);
+impl_vector_op!(Div, div, /);
+
+impl Mul<&Vector> for f64 {
+    type Output = Vector;
+
+    fn mul(self, vector: &Vector) -> Vector {
+        vector * self
+    }
+}
+
+impl Mul<Vector> for f64 {
+    type Output = Vector;
+
+    fn mul(self, vector: Vector) -> Vector {
+        &vector * self
+    }
+}
+
+impl Neg for &Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        self.map(|val| -val)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        -&self
+    }
+}
+
+impl Mul<&Vector> for &Matrix {
+    type Output = Vector;
+
+    fn mul(self, vector: &Vector) -> Vector {
+        self.mul_vector(vector).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Mul<Vector> for &Matrix {
+    type Output = Vector;
+
+    fn mul(self, vector: Vector) -> Vector {
+        self * &vector
+    }
+}
+
+impl Mul<&Vector> for Matrix {
+    type Output = Vector;
+
+    fn mul(self, vector: &Vector) -> Vector {
+        &self * vector
+    }
+}
+
+impl Mul<Vector> for Matrix {
+    type Output = Vector;
+
+    fn mul(self, vector: Vector) -> Vector {
+        &self * &vector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn construction_and_conversions() {
+        let v = Vector::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(v, Vector::from(vec![1.0, 2.0, 3.0]));
+        assert_eq!(v, Vector::from_fn(3, |i| (i + 1) as f64));
+        assert_eq!(v, [1.0, 2.0, 3.0].iter().copied().collect());
+        assert_eq!(Vector::zeros(2).as_slice(), [0.0, 0.0]);
+        assert_eq!(v.clone().into_vec(), [1.0, 2.0, 3.0]);
+
+        let empty = Vector::default();
+        assert!(empty.is_empty());
+        assert_eq!(empty.norm(), 0.0);
+        assert_eq!(empty.dot(&empty), Ok(0.0));
+    }
+
+    #[test]
+    fn dot_and_cross_check_lengths() {
+        let a = Vector::new(vec![1.0, 2.0, 3.0]);
+        let b = Vector::new(vec![4.0, 5.0, 6.0]);
+        assert_eq!(a.dot(&b), Ok(32.0));
+        assert_eq!(a.cross(&b), Ok(Vector::new(vec![-3.0, 6.0, -3.0])));
+
+        let short = Vector::new(vec![1.0, 2.0]);
+        let mismatch = LinalgError::DimensionMismatch {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(a.dot(&short), Err(mismatch.clone()));
+        assert_eq!(short.cross(&a), Err(mismatch));
+    }
+
+    #[test]
+    fn normalize_rejects_the_zero_vector() {
+        let v = Vector::new(vec![3.0, 4.0]);
+        assert_eq!(v.norm(), 5.0);
+        assert_eq!(v.normalize(), Ok(Vector::new(vec![0.6, 0.8])));
+        assert_eq!(Vector::zeros(3).normalize(), Err(LinalgError::ZeroNorm));
+    }
+
+    #[test]
+    fn column_matrix_transposes_to_a_row() {
+        let v = Vector::new(vec![1.0, 2.0, 3.0]);
+        let column = v.to_column_matrix();
+        assert_eq!(column.shape(), (3, 1));
+        let row = column.transpose();
+        assert_eq!(row.shape(), (1, 3));
+        assert_eq!(row.row(0), v.as_slice());
+        assert_eq!(row.mul_vector(&v), Ok(Vector::new(vec![14.0])));
+    }
+
+    #[test]
+    fn matrix_vector_product_checks_the_length() {
+        let m = Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+        let v = Vector::new(vec![1.0, 1.0]);
+        assert_eq!(m.mul_vector(&v), Ok(Vector::new(vec![3.0, 7.0])));
+        assert_eq!(&m * &v, Vector::new(vec![3.0, 7.0]));
+        assert_eq!(
+            m.mul_vector(&[1.0, 2.0, 3.0]),
+            Err(LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn elementwise_operators() {
+        let a = Vector::new(vec![1.0, 2.0]);
+        let b = Vector::new(vec![3.0, 5.0]);
+        assert_eq!(&a + &b, Vector::new(vec![4.0, 7.0]));
+        assert_eq!(&b - &a, Vector::new(vec![2.0, 3.0]));
+        assert_eq!(&a * &b, Vector::new(vec![3.0, 10.0]));
+        assert_eq!(2.0 * &a, Vector::new(vec![2.0, 4.0]));
+        assert_eq!(-a, Vector::new(vec![-1.0, -2.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn operators_panic_on_length_mismatch() {
+        let _ = Vector::zeros(2) + Vector::zeros(3);
+    }
+}
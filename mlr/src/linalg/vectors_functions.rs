@@ -1,4 +1,7 @@
 use std::io::{self, Write};
+
+use crate::error::LinalgError;
+
 pub fn ask_vector() -> Vec<f64> {
     let mut vec: Vec<f64> = Vec::new();
 
@@ -43,40 +46,49 @@ pub fn ask_vector() -> Vec<f64> {
     vec
 }
 
-pub fn vec_wedge_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<f64, String> {
+pub fn vec_wedge_prod(vec1: &[f64], vec2: &[f64]) -> Result<f64, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors need to be the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
-    if vec1.len() != 2 || vec2.len() != 2 {
-        return Err("Vectors need to be 2-dimensional".to_string());
+    if vec1.len() != 2 {
+        return Err(LinalgError::DimensionMismatch {
+            expected: 2,
+            found: vec1.len(),
+        });
     }
 
     let result = vec1[0] * vec2[1] - vec1[1] * vec2[0];
     Ok(result)
 }
 
-pub fn vec_alg_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<f64, String> {
+pub fn vec_alg_prod(vec1: &[f64], vec2: &[f64]) -> Result<f64, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors need to be the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
-    let dot_prod = vec_dot_prod(vec1.clone(), vec2.clone())?; // Clone to avoid ownership issues
+    let dot_prod = vec_dot_prod(vec1, vec2)?;
     let wedge_prod = vec_wedge_prod(vec1, vec2)?;
 
     // The geometric product is the sum of dot and wedge product
     Ok(dot_prod + wedge_prod)
 }
 
-pub fn vec_tensor_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<Vec<f64>>, String> {
+pub fn vec_tensor_prod(vec1: &[f64], vec2: &[f64]) -> Result<Vec<Vec<f64>>, LinalgError> {
     if vec1.is_empty() || vec2.is_empty() {
-        return Err("Vectors cannot be empty".to_string());
+        return Err(LinalgError::Empty);
     }
 
     let mut mat_res: Vec<Vec<f64>> = Vec::new();
 
-    for &val1 in &vec1 {
+    for &val1 in vec1 {
         let mut row: Vec<f64> = Vec::new();
-        for &val2 in &vec2 {
+        for &val2 in vec2 {
             row.push(val1 * val2);
         }
         mat_res.push(row);
@@ -85,9 +97,12 @@ pub fn vec_tensor_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<Vec<f64>>,
     Ok(mat_res)
 }
 
-pub fn vec_exterior_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<Vec<f64>>, String> {
+pub fn vec_exterior_prod(vec1: &[f64], vec2: &[f64]) -> Result<Vec<Vec<f64>>, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors must have the same length.".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
     let n = vec1.len();
@@ -105,9 +120,12 @@ pub fn vec_exterior_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<Vec<f64>>
     Ok(result)
 }
 
-pub fn vec_cross_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<f64>, String> {
-    if vec1.len() != 3 || vec2.len() != 3 {
-        return Err("Both vectors need to be 3-dimensional.".to_string());
+pub fn vec_cross_prod(vec1: &[f64], vec2: &[f64]) -> Result<Vec<f64>, LinalgError> {
+    if let Some(other) = [vec1.len(), vec2.len()].into_iter().find(|&len| len != 3) {
+        return Err(LinalgError::DimensionMismatch {
+            expected: 3,
+            found: other,
+        });
     }
 
     let cross_prod = vec![
@@ -119,131 +137,144 @@ pub fn vec_cross_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<f64>, String
     Ok(cross_prod)
 }
 
-pub fn vec_dot_prod(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<f64, String> {
+pub fn vec_dot_prod(vec1: &[f64], vec2: &[f64]) -> Result<f64, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors must have the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
     let sum: f64 = vec1
-        .into_iter()
-        .zip(vec2.into_iter())
+        .iter()
+        .zip(vec2.iter())
         .map(|(x, y)| x * y)
         .sum();
 
     Ok(sum)
 }
 
-pub fn vec_add(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<f64>, String> {
+pub fn vec_add(vec1: &[f64], vec2: &[f64]) -> Result<Vec<f64>, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors must have the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
     let result = vec1
-        .into_iter()
-        .zip(vec2.into_iter())
+        .iter()
+        .zip(vec2.iter())
         .map(|(x, y)| x + y)
         .collect();
 
     Ok(result)
 }
 
-pub fn vec_mul(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<f64>, String> {
+pub fn vec_mul(vec1: &[f64], vec2: &[f64]) -> Result<Vec<f64>, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors must have the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
     let result = vec1
-        .into_iter()
-        .zip(vec2.into_iter())
+        .iter()
+        .zip(vec2.iter())
         .map(|(x, y)| x * y)
         .collect();
 
     Ok(result)
 }
 
-pub fn vec_sub(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<f64>, String> {
+pub fn vec_sub(vec1: &[f64], vec2: &[f64]) -> Result<Vec<f64>, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors must have the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
     let result = vec1
-        .into_iter()
-        .zip(vec2.into_iter())
+        .iter()
+        .zip(vec2.iter())
         .map(|(x, y)| x - y)
         .collect();
 
     Ok(result)
 }
 
-pub fn vec_div(vec1: Vec<f64>, vec2: Vec<f64>) -> Result<Vec<f64>, String> {
+pub fn vec_div(vec1: &[f64], vec2: &[f64]) -> Result<Vec<f64>, LinalgError> {
     if vec1.len() != vec2.len() {
-        return Err("Vectors must have the same length".to_string());
+        return Err(LinalgError::DimensionMismatch {
+            expected: vec1.len(),
+            found: vec2.len(),
+        });
     }
 
     let result = vec1
-        .into_iter()
-        .zip(vec2.into_iter())
+        .iter()
+        .zip(vec2.iter())
         .map(|(x, y)| x / y)
         .collect();
 
     Ok(result)
 }
 
-pub fn vec_by_scalar(vec: Vec<f64>, scalar: f64) -> Vec<f64> {
-    let result = vec.iter().map(|a| a * scalar).collect();
-
-    result
+pub fn vec_by_scalar(vec: &[f64], scalar: f64) -> Vec<f64> {
+    vec.iter().map(|a| a * scalar).collect()
 }
 pub fn test_vec() {
     let vec_test1 = vec![1.09, 3.89, 7.90];
     let vec_test2 = vec![3.89, 2.90, 1.56];
 
-    match vec_add(vec_test1.clone(), vec_test2.clone()) {
+    match vec_add(&vec_test1, &vec_test2) {
         Ok(result) => println!("{:?} + {:?} = {:?}", vec_test1, vec_test2, result),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_mul(vec_test1.clone(), vec_test2.clone()) {
+    match vec_mul(&vec_test1, &vec_test2) {
         Ok(result) => println!("{:?} times {:?} = {:?}", vec_test1, vec_test2, result),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_sub(vec_test1.clone(), vec_test2.clone()) {
+    match vec_sub(&vec_test1, &vec_test2) {
         Ok(result) => println!("{:?} - {:?} = {:?}", vec_test1, vec_test2, result),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_div(vec_test1.clone(), vec_test2.clone()) {
+    match vec_div(&vec_test1, &vec_test2) {
         Ok(result) => println!("{:?} / {:?} = {:?}", vec_test1, vec_test2, result),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_dot_prod(vec_test1.clone(), vec_test2.clone()) {
+    match vec_dot_prod(&vec_test1, &vec_test2) {
         Ok(sum) => println!("{:?} dot_prod {:?} = {}", vec_test1, vec_test2, sum),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_cross_prod(vec_test1.clone(), vec_test2.clone()) {
+    match vec_cross_prod(&vec_test1, &vec_test2) {
         Ok(cross_prod) => println!(
             "{:?} cross_prod {:?} = {:?}",
             vec_test1, vec_test2, cross_prod
         ),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_exterior_prod(vec_test1.clone(), vec_test2.clone()) {
+    match vec_exterior_prod(&vec_test1, &vec_test2) {
         Ok(result) => println!(
             "{:?} exterior_prod {:?} = {:?}",
             vec_test1, vec_test2, result
         ),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_tensor_prod(vec_test1.clone(), vec_test2.clone()) {
+    match vec_tensor_prod(&vec_test1, &vec_test2) {
         Ok(mat_res) => println!(
             "{:?} tensor_prod {:?} = {:?}",
             vec_test1, vec_test2, mat_res
         ),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_wedge_prod(vec_test1.clone(), vec_test2.clone()) {
+    match vec_wedge_prod(&vec_test1, &vec_test2) {
         Ok(resoult) => println!("{:?} wedge_prod{:?} = {:?}", vec_test1, vec_test2, resoult),
         Err(e) => println!("Error: {}", e),
     }
-    match vec_alg_prod(vec_test1.clone(), vec_test2.clone()) {
+    match vec_alg_prod(&vec_test1, &vec_test2) {
         Ok(resoult) => println!("{:?} alg_prod {:?} = {:?}", vec_test1, vec_test2, resoult),
         Err(e) => println!("Error: {}", e),
     }
@@ -1,33 +1,1230 @@
-mod datagen;
-mod simple_li_reg_model;
-mod visualization; // Import the visualization module
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use mlr::datagen::{self, ClassificationGenerator, Noise, RegressionGenerator, Shape};
+use mlr::dataset::{Column, MissingValues};
+use mlr::metrics::{self, Average};
+use mlr::model_selection::{self, Fold};
+use mlr::optim;
+use mlr::persist::{SavedModel, TrainingMetadata};
+use mlr::prelude::*;
+use mlr::visualization;
+
+/// Train, apply and evaluate linear regression and classification models on
+/// CSV data.
+///
+/// Run without a subcommand to see the built-in demo on synthetic data.
+#[derive(Parser)]
+#[command(name = "mlr", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Seed for everything random: initial weights, shuffling, train/test
+    /// and cross-validation splits, and synthetic data
+    #[arg(long, global = true, default_value_t = 42)]
+    seed: u64,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Fit a model to a CSV file and save it
+    Train(Box<TrainArgs>),
+    /// Print predictions of a saved model for every row of a CSV file
+    Predict(PredictArgs),
+    /// Score a saved model against a CSV file that has the target column
+    Eval(EvalArgs),
+    /// Write a synthetic regression or classification dataset to CSV
+    Generate(GenerateArgs),
+    /// Train on synthetic data and plot the result (the default)
+    Demo,
+}
+
+#[derive(Args)]
+struct CsvArgs {
+    /// CSV file to read
+    #[arg(long)]
+    data: PathBuf,
+    /// Field delimiter
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+    /// Treat the first row as data even if it looks like a header
+    #[arg(long)]
+    no_header: bool,
+    /// Skip rows with missing values instead of failing (not for predict,
+    /// whose output must line up with the input rows)
+    #[arg(long)]
+    drop_missing: bool,
+}
+
+#[derive(Args)]
+struct TrainArgs {
+    #[command(flatten)]
+    csv: CsvArgs,
+    /// Target column, by header name or zero-based index
+    #[arg(long)]
+    target: String,
+    /// Comma-separated feature columns (default: every other column)
+    #[arg(long, value_delimiter = ',')]
+    features: Option<Vec<String>>,
+    /// Kind of model to fit
+    #[arg(long, value_enum, default_value_t = ModelKind::Linear)]
+    model: ModelKind,
+    /// Highest power of the features for --model polynomial
+    #[arg(long, default_value_t = 2)]
+    degree: usize,
+    /// Gradient descent learning rate
+    #[arg(long, default_value_t = 0.01)]
+    lr: f64,
+    /// Gradient descent epochs
+    #[arg(long, default_value_t = 100000)]
+    epochs: usize,
+    /// Parameter update rule
+    #[arg(long, value_enum, default_value_t = OptimizerKind::Sgd)]
+    optimizer: OptimizerKind,
+    /// Velocity decay for the momentum and nesterov optimizers
+    #[arg(long, default_value_t = 0.9)]
+    momentum: f64,
+    /// Samples per step, reshuffled every epoch (default: the whole dataset)
+    #[arg(long)]
+    batch_size: Option<usize>,
+    /// Stop once the loss changes by less than this between epochs; for
+    /// coordinate descent, once no weight moves by more than this (default
+    /// 1e-8 there, off for gradient descent)
+    #[arg(long)]
+    tol: Option<f64>,
+    /// Stop once the gradient norm falls below this
+    #[arg(long)]
+    grad_tol: Option<f64>,
+    /// Stop after this many epochs without a better validation loss
+    #[arg(long)]
+    patience: Option<usize>,
+    /// Share of the rows held out for the validation loss used by --patience
+    #[arg(long, default_value_t = 0.1)]
+    validation_fraction: f64,
+    /// Stop after training for this many seconds
+    #[arg(long, value_parser = parse_seconds)]
+    max_seconds: Option<f64>,
+    /// Plot the per-epoch loss to this PNG or SVG file
+    #[arg(long)]
+    loss_plot: Option<PathBuf>,
+    /// Use a logarithmic loss axis in --loss-plot
+    #[arg(long, requires = "loss_plot")]
+    log_scale: bool,
+    /// Save the model even if gradient descent diverged
+    #[arg(long)]
+    save_diverged: bool,
+    /// Regularization on the weights
+    #[arg(long, value_enum, default_value_t = PenaltyKind::None)]
+    penalty: PenaltyKind,
+    /// Regularization strength
+    #[arg(long, default_value_t = 1.0)]
+    alpha: f64,
+    /// Share of the elastic-net penalty that is L1
+    #[arg(long, default_value_t = 0.5)]
+    l1_ratio: f64,
+    /// Comma-separated feature columns holding category names rather than
+    /// numbers
+    #[arg(long, value_delimiter = ',')]
+    categorical: Vec<String>,
+    /// How --categorical columns are turned into model inputs
+    #[arg(long, value_enum, default_value_t = EncodingKind::OneHot)]
+    encoding: EncodingKind,
+    /// What predict and eval do with a category the model wasn't trained on
+    #[arg(long, value_enum, default_value_t = UnknownKind::Error)]
+    unknown: UnknownKind,
+    /// Fill in missing values instead of failing on them
+    #[arg(long, value_enum, conflicts_with = "drop_missing")]
+    impute: Option<ImputeKind>,
+    /// Value filled in by --impute constant
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    fill_value: f64,
+    /// Rescale the features before fitting
+    #[arg(long, value_enum)]
+    scale: Option<ScaleKind>,
+    /// Fit exactly instead of running gradient descent: the normal equation,
+    /// or coordinate descent (up to --epochs sweeps) for lasso and elastic net
+    #[arg(long)]
+    closed_form: bool,
+    /// Hold out this share of the rows, fit on the rest and report the
+    /// held-out MSE
+    #[arg(long)]
+    test_fraction: Option<f64>,
+    /// Report k-fold cross-validated scores before the final fit (MSE and R²
+    /// for regression, stratified accuracy for classifiers)
+    #[arg(long)]
+    cv_folds: Option<usize>,
+    /// Where to write the trained model: JSON if it ends in `.json`,
+    /// the compact binary format otherwise
+    #[arg(long, default_value = "model.json")]
+    out: PathBuf,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OptimizerKind {
+    Sgd,
+    Momentum,
+    Nesterov,
+    Rmsprop,
+    Adam,
+}
+
+impl OptimizerKind {
+    fn build(self, momentum: f64) -> optim::OptimizerKind {
+        match self {
+            OptimizerKind::Sgd => optim::OptimizerKind::Sgd,
+            OptimizerKind::Momentum => optim::OptimizerKind::Momentum { beta: momentum },
+            OptimizerKind::Nesterov => optim::OptimizerKind::Nesterov { beta: momentum },
+            OptimizerKind::Rmsprop => optim::OptimizerKind::RmsProp,
+            OptimizerKind::Adam => optim::OptimizerKind::Adam,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ModelKind {
+    /// Linear regression
+    Linear,
+    /// Linear regression on polynomial features of degree --degree
+    Polynomial,
+    /// Binary classifier; the target must hold 0 or 1
+    Logistic,
+    /// Multi-class classifier; the target must hold class indices 0, 1, 2, ...
+    Softmax,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum EncodingKind {
+    /// One 0/1 column per category except the first, which the intercept
+    /// stands for
+    OneHot,
+    /// A single column with the rank of the category in sorted order
+    Ordinal,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum UnknownKind {
+    /// Fail
+    Error,
+    /// Encode like the first category (one-hot) or as -1 (ordinal)
+    Ignore,
+}
+
+impl UnknownKind {
+    fn build(self) -> HandleUnknown {
+        match self {
+            UnknownKind::Error => HandleUnknown::Error,
+            UnknownKind::Ignore => HandleUnknown::Ignore,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ImputeKind {
+    Mean,
+    Median,
+    MostFrequent,
+    /// The value of --fill-value
+    Constant,
+}
+
+impl ImputeKind {
+    fn build(self, fill_value: f64) -> ImputeStrategy {
+        match self {
+            ImputeKind::Mean => ImputeStrategy::Mean,
+            ImputeKind::Median => ImputeStrategy::Median,
+            ImputeKind::MostFrequent => ImputeStrategy::MostFrequent,
+            ImputeKind::Constant => ImputeStrategy::Constant(fill_value),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ScaleKind {
+    /// Zero mean and unit variance per feature
+    Standard,
+    /// Every feature mapped onto [0, 1]
+    Minmax,
+    /// Zero median and unit interquartile range per feature
+    Robust,
+    /// Every row scaled to unit Euclidean length
+    Normalize,
+}
+
+impl ScaleKind {
+    fn build(self) -> FeatureTransform {
+        match self {
+            ScaleKind::Standard => StandardScaler::new().into(),
+            ScaleKind::Minmax => MinMaxScaler::new().into(),
+            ScaleKind::Robust => RobustScaler::new().into(),
+            ScaleKind::Normalize => Normalizer::new(Norm::L2).into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PenaltyKind {
+    None,
+    Ridge,
+    Lasso,
+    ElasticNet,
+}
+
+impl PenaltyKind {
+    fn build(self, alpha: f64, l1_ratio: f64) -> Penalty {
+        match self {
+            PenaltyKind::None => Penalty::None,
+            PenaltyKind::Ridge => Penalty::Ridge(alpha),
+            PenaltyKind::Lasso => Penalty::Lasso(alpha),
+            PenaltyKind::ElasticNet => Penalty::ElasticNet { alpha, l1_ratio },
+        }
+    }
+}
+
+#[derive(Args)]
+struct PredictArgs {
+    #[command(flatten)]
+    csv: CsvArgs,
+    /// Model written by `mlr train`
+    #[arg(long)]
+    model: PathBuf,
+    /// Write predictions to this file instead of stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct EvalArgs {
+    #[command(flatten)]
+    csv: CsvArgs,
+    /// Model written by `mlr train`
+    #[arg(long)]
+    model: PathBuf,
+    /// Target column (default: the one the model was trained on)
+    #[arg(long)]
+    target: Option<String>,
+    /// Error margin for counting a prediction as close
+    #[arg(long, default_value_t = 0.1)]
+    tolerance: f64,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Layout of the data: a linear target or labelled classes
+    #[arg(long, value_enum, default_value_t = DataKind::Regression)]
+    kind: DataKind,
+    /// Number of rows
+    #[arg(long, default_value_t = 100)]
+    samples: usize,
+    /// Number of feature columns for regression, blobs and separable
+    /// (default: 1 for regression, 2 otherwise)
+    #[arg(long)]
+    features: Option<usize>,
+    /// Only this many features get a non-zero weight (default: all)
+    #[arg(long)]
+    informative: Option<usize>,
+    /// Comma-separated true weights, one per feature, which also sets the
+    /// number of features (default: drawn uniformly from [-5, 5))
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    coefficients: Option<Vec<f64>>,
+    /// True intercept
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    intercept: f64,
+    /// Lower end of the range features are drawn from
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    low: f64,
+    /// Upper end of the range features are drawn from
+    #[arg(long, default_value_t = 10.0, allow_hyphen_values = true)]
+    high: f64,
+    /// Kind of noise added to the target
+    #[arg(long, value_enum, default_value_t = NoiseKind::None)]
+    noise: NoiseKind,
+    /// Standard deviation (gaussian), half-width (uniform) or relative
+    /// standard deviation (heteroscedastic) of the noise
+    #[arg(long, default_value_t = 1.0)]
+    noise_level: f64,
+    /// Share of the rows whose target is moved by ±--outlier-magnitude
+    #[arg(long, default_value_t = 0.0)]
+    outliers: f64,
+    #[arg(long, default_value_t = 10.0)]
+    outlier_magnitude: f64,
+    /// Number of blobs
+    #[arg(long, default_value_t = 3)]
+    classes: usize,
+    /// Standard deviation of each blob
+    #[arg(long, default_value_t = 1.0)]
+    cluster_std: f64,
+    /// Radius of the inner circle relative to the outer one
+    #[arg(long, default_value_t = 0.5)]
+    factor: f64,
+    /// Minimum gap between separable classes
+    #[arg(long, default_value_t = 0.5)]
+    margin: f64,
+    /// Standard deviation of Gaussian noise added to every feature of
+    /// classification data
+    #[arg(long, default_value_t = 0.0)]
+    feature_noise: f64,
+    /// Comma-separated relative class sizes, e.g. 9,1 (default: balanced)
+    #[arg(long, value_delimiter = ',')]
+    class_weights: Option<Vec<f64>>,
+    /// CSV file to write
+    #[arg(long)]
+    out: PathBuf,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DataKind {
+    /// Linear target with known coefficients (column `y`)
+    Regression,
+    /// Gaussian clusters, one per class (column `label`)
+    Blobs,
+    /// Two interleaving half circles
+    Moons,
+    /// A small circle inside a larger one
+    Circles,
+    /// Opposite quadrants share a class
+    Xor,
+    /// Two classes on either side of a random hyperplane
+    Separable,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum NoiseKind {
+    None,
+    Gaussian,
+    Uniform,
+    Heteroscedastic,
+}
+
+impl NoiseKind {
+    fn build(self, level: f64) -> Noise {
+        match self {
+            NoiseKind::None => Noise::None,
+            NoiseKind::Gaussian => Noise::Gaussian { std: level },
+            NoiseKind::Uniform => Noise::Uniform { half_width: level },
+            NoiseKind::Heteroscedastic => Noise::Heteroscedastic { std: level },
+        }
+    }
+}
+
+// A time limit must be positive and small enough for a `Duration`.
+fn parse_seconds(arg: &str) -> Result<f64, String> {
+    let seconds: f64 = arg.parse().map_err(|e| format!("{}", e))?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(_) if seconds > 0.0 => Ok(seconds),
+        _ => Err(format!(
+            "{} is not a positive number of seconds in range",
+            arg
+        )),
+    }
+}
+
+// Column arguments that are plain numbers select by index.
+fn parse_column(arg: &str) -> Column {
+    match arg.parse::<usize>() {
+        Ok(index) => Column::Index(index),
+        Err(_) => Column::Name(arg.to_string()),
+    }
+}
+
+fn csv_options(args: &CsvArgs, target: Option<&str>) -> CsvOptions {
+    let mut options = match target {
+        Some(target) => CsvOptions::new(parse_column(target)),
+        None => CsvOptions::without_target(),
+    }
+    .delimiter(args.delimiter);
+
+    if args.no_header {
+        options = options.has_header(false);
+    }
+    if args.drop_missing {
+        options = options.missing(MissingValues::DropRows);
+    }
+    options
+}
+
+fn load_dataset(args: &CsvArgs, options: &CsvOptions) -> Result<Dataset, Box<dyn Error>> {
+    let data = Dataset::from_csv_path(&args.data, options)?;
+    if data.missing.dropped_rows > 0 {
+        eprintln!(
+            "Dropped {} rows with missing values: {:?}",
+            data.missing.dropped_rows, data.missing.per_column
+        );
+    }
+    Ok(data)
+}
+
+fn train(args: &TrainArgs, seed: u64) -> Result<(), Box<dyn Error>> {
+    let mut options = csv_options(&args.csv, Some(&args.target));
+    for column in &args.categorical {
+        options = options.categorical(parse_column(column));
+    }
+    if args.impute.is_some() {
+        options = options.missing(MissingValues::Keep);
+    }
+    if let Some(features) = &args.features {
+        options = options.features(features.iter().map(|f| parse_column(f)));
+    }
+    let mut data = load_dataset(&args.csv, &options)?;
+    let target = std::mem::take(&mut data.target);
+    let penalty = args.penalty.build(args.alpha, args.l1_ratio);
+
+    let solver = if args.closed_form {
+        Solver::Exact {
+            penalty,
+            max_iter: args.epochs,
+            tolerance: coordinate_descent_tolerance(args),
+        }
+    } else {
+        Solver::GradientDescent(gradient_descent(args, penalty, seed))
+    };
+    if args.closed_form && matches!(args.model, ModelKind::Logistic | ModelKind::Softmax) {
+        return Err("--closed-form only applies to regression models".into());
+    }
+
+    match args.model {
+        ModelKind::Linear => {
+            preprocess_and_fit::<MultiLinRegModel, _>(args, seed, data, target, solver)
+        }
+        ModelKind::Polynomial => {
+            let params = PolyRegParams::new(PolynomialFeatures::new(args.degree)).solver(solver);
+            preprocess_and_fit::<PolyRegModel, _>(args, seed, data, target, params)
+        }
+        ModelKind::Logistic => {
+            let labels = class_labels(&target)?;
+            let params = gradient_descent(args, penalty, seed);
+            preprocess_and_fit::<LogisticRegression, _>(args, seed, data, labels, params)
+        }
+        ModelKind::Softmax => {
+            let labels = class_labels(&target)?;
+            // From every label, before any split can leave a class out
+            let n_classes = labels.iter().max().map_or(0, |&label| label + 1);
+            let params = SoftmaxParams::new(n_classes, gradient_descent(args, penalty, seed));
+            preprocess_and_fit::<SoftmaxRegression, _>(args, seed, data, labels, params)
+        }
+    }
+}
+
+// Puts the `--impute`, `--categorical` and `--scale` steps, if any, in front
+// of the model before fitting. The steps see the features in that order.
+fn preprocess_and_fit<M, T>(
+    args: &TrainArgs,
+    seed: u64,
+    data: Dataset,
+    y: Vec<T>,
+    params: M::Params,
+) -> Result<(), Box<dyn Error>>
+where
+    M: Fit<Target = T> + Predict<Output = T> + Into<SavedModel>,
+    T: TargetKind,
+{
+    let mut steps: Vec<FeatureTransform> = Vec::new();
+    let columns: Vec<usize> = (0..data.n_features())
+        .filter(|&j| !data.categories[j].is_empty())
+        .collect();
+    if let Some(impute) = args.impute {
+        let imputer =
+            SimpleImputer::new(impute.build(args.fill_value)).categorical(columns.clone());
+        steps.push(imputer.into());
+    }
+    if !columns.is_empty() {
+        let names = columns
+            .iter()
+            .map(|&j| data.categories[j].clone())
+            .collect();
+        let unknown = args.unknown.build();
+        steps.push(match args.encoding {
+            EncodingKind::OneHot => OneHotEncoder::new(columns)
+                .handle_unknown(unknown)
+                .drop_first(true)
+                .category_names(names)
+                .into(),
+            EncodingKind::Ordinal => OrdinalEncoder::new(columns)
+                .handle_unknown(unknown)
+                .category_names(names)
+                .into(),
+        });
+    }
+    if let Some(scale) = args.scale {
+        steps.push(scale.build());
+    }
+
+    if steps.is_empty() {
+        return fit_and_save::<M, T>(args, seed, data, y, &params);
+    }
+    let params = PipelineParams {
+        steps,
+        model: params,
+    };
+    fit_and_save::<Pipeline<M>, T>(args, seed, data, y, &params)
+}
+
+// Cross-validates, fits and saves any model; `data.target` has already been
+// converted to `y`.
+fn fit_and_save<M, T>(
+    args: &TrainArgs,
+    seed: u64,
+    mut data: Dataset,
+    mut y: Vec<T>,
+    params: &M::Params,
+) -> Result<(), Box<dyn Error>>
+where
+    M: Fit<Target = T> + Predict<Output = T> + Into<SavedModel>,
+    T: TargetKind,
+{
+    if let Some(k) = args.cv_folds {
+        let folds = T::folds(&y, k, seed)?;
+        let metrics: Vec<Metric<T>> = T::METRICS.iter().map(|&(_, metric)| metric).collect();
+        let all_scores = model_selection::cross_validate_multi::<M, _>(
+            &data.features,
+            &y,
+            &folds,
+            params,
+            &metrics,
+        )?;
+        for (&(name, _), scores) in T::METRICS.iter().zip(all_scores) {
+            println!(
+                "{}-fold cross-validation {}: {:.5} ± {:.5}",
+                k,
+                name,
+                scores.mean(),
+                scores.std()
+            );
+        }
+    }
+
+    let mut held_out = None;
+    if let Some(fraction) = args.test_fraction {
+        let split = model_selection::train_test_split(&data.features, &y, fraction, seed)?;
+        data.features = split.x_train;
+        y = split.y_train;
+        held_out = Some((split.x_test, split.y_test));
+    }
+
+    let (model, report) = M::fit_with_report(&data.features, &y, params)?;
+    if let Some(report) = &report {
+        println!(
+            "Stopped after {} epochs in {:.2?}: {}",
+            report.epochs_run, report.elapsed, report.stop_reason
+        );
+        if let Some(loss) = report.best_validation_loss {
+            println!("Best validation loss: {:.5}", loss);
+        }
+        if let (Some(path), Some(history)) = (&args.loss_plot, &report.history) {
+            visualization::plot_loss_curve(history, path, args.log_scale)?;
+            println!("Loss curve saved to {}", path.display());
+        }
+        if report.stop_reason == StopReason::Diverged && !args.save_diverged {
+            return Err(
+                "training diverged, so the model was not saved: try a smaller \
+                 --lr, or pass --save-diverged to save it anyway"
+                    .into(),
+            );
+        }
+    }
+
+    let predictions = model.predict(&data.features)?;
+    let (metric_name, metric) = T::METRICS[0];
+    println!(
+        "Trained on {} samples, {} features",
+        y.len(),
+        data.n_features()
+    );
+    println!("Training {}: {:.5}", metric_name, metric(&y, &predictions)?);
+    if let Some((x_test, y_test)) = &held_out {
+        let test_predictions = model.predict(x_test)?;
+        println!(
+            "Test {} on {} held-out samples: {:.5}",
+            metric_name,
+            y_test.len(),
+            metric(y_test, &test_predictions)?
+        );
+    }
+
+    let mut metadata = TrainingMetadata::new(
+        data.feature_names,
+        data.target_name.unwrap_or_default(),
+        y.len(),
+    );
+    metadata.final_loss = T::training_loss(&y, &predictions, report.as_ref());
+
+    let file = with_hyperparameters(ModelFile::new(model, metadata), args, seed);
+    file.save(&args.out)?;
+    println!("Model saved to {}", args.out.display());
+
+    Ok(())
+}
+
+// Records the settings `train` fitted with.
+fn with_hyperparameters(mut file: ModelFile, args: &TrainArgs, seed: u64) -> ModelFile {
+    let penalty = args.penalty.build(args.alpha, args.l1_ratio);
+    if args.model == ModelKind::Polynomial {
+        file = file.with_hyperparameter("degree", args.degree);
+    }
+    if let Some(impute) = args.impute {
+        let strategy = impute.build(args.fill_value);
+        file = file.with_hyperparameter("impute", strategy.name());
+        if let ImputeStrategy::Constant(value) = strategy {
+            file = file.with_hyperparameter("fill_value", value);
+        }
+    }
+    if !args.categorical.is_empty() {
+        let encoding = match args.encoding {
+            EncodingKind::OneHot => "one_hot",
+            EncodingKind::Ordinal => "ordinal",
+        };
+        file = file
+            .with_hyperparameter("categorical", args.categorical.join(","))
+            .with_hyperparameter("encoding", encoding);
+        if let HandleUnknown::Ignore = args.unknown.build() {
+            file = file.with_hyperparameter("unknown", "ignore");
+        }
+    }
+    if let Some(scale) = args.scale {
+        file = file.with_hyperparameter("scale", scale.build().type_name());
+    }
+    file = file.with_hyperparameter("penalty", penalty.name());
+    if penalty != Penalty::None {
+        file = file.with_hyperparameter("alpha", args.alpha);
+    }
+    if let Penalty::ElasticNet { l1_ratio, .. } = penalty {
+        file = file.with_hyperparameter("l1_ratio", l1_ratio);
+    }
+
+    file = if args.closed_form && penalty.has_l1() {
+        file.with_hyperparameter("method", "coordinate_descent")
+            .with_hyperparameter("max_iter", args.epochs)
+            .with_hyperparameter("tolerance", coordinate_descent_tolerance(args))
+    } else if args.closed_form {
+        file.with_hyperparameter("method", "closed_form")
+    } else {
+        let mut file = file
+            .with_hyperparameter("method", "gradient_descent")
+            .with_hyperparameter(
+                "optimizer",
+                args.optimizer.build(args.momentum).build(args.lr).name(),
+            )
+            .with_hyperparameter("learning_rate", args.lr)
+            .with_hyperparameter("epochs", args.epochs);
+
+        let optional = [
+            ("loss_tolerance", args.tol.map(|v| v.to_string())),
+            ("batch_size", args.batch_size.map(|v| v.to_string())),
+            ("grad_tolerance", args.grad_tol.map(|v| v.to_string())),
+            ("patience", args.patience.map(|v| v.to_string())),
+            ("max_seconds", args.max_seconds.map(|v| v.to_string())),
+        ];
+        for (name, value) in optional {
+            if let Some(value) = value {
+                file = file.with_hyperparameter(name, value);
+            }
+        }
+        if args.patience.is_some() {
+            file = file.with_hyperparameter("validation_fraction", args.validation_fraction);
+        }
+        file
+    };
+    if let Some(fraction) = args.test_fraction {
+        file = file.with_hyperparameter("test_fraction", fraction);
+    }
+    // Closed-form fits only use the seed to split off the test set
+    if !args.closed_form || args.test_fraction.is_some() {
+        file = file.with_hyperparameter("seed", seed);
+    }
+    file
+}
+
+// `--tol` for coordinate descent, which always needs one; the default is
+// that of `Solver::exact`.
+fn coordinate_descent_tolerance(args: &TrainArgs) -> f64 {
+    args.tol.unwrap_or(1e-8)
+}
+
+fn gradient_descent(args: &TrainArgs, penalty: Penalty, seed: u64) -> GradientDescent {
+    let mut config = TrainConfig::new(args.epochs)
+        .penalty(penalty)
+        .validation_fraction(args.validation_fraction)
+        .seed(seed);
+    if let Some(tolerance) = args.tol {
+        config = config.loss_tolerance(tolerance);
+    }
+    if let Some(size) = args.batch_size {
+        config = config.batch_size(size);
+    }
+    if let Some(tolerance) = args.grad_tol {
+        config = config.grad_tolerance(tolerance);
+    }
+    if let Some(patience) = args.patience {
+        config = config.patience(patience);
+    }
+    if let Some(seconds) = args.max_seconds {
+        config = config.max_duration(Duration::from_secs_f64(seconds));
+    }
+    if args.loss_plot.is_some() {
+        config = config.record_history();
+    }
+
+    GradientDescent::new(args.lr, args.epochs)
+        .optimizer(args.optimizer.build(args.momentum))
+        .config(config)
+}
+
+type Metric<T> = fn(&[T], &[T]) -> Result<f64, MetricsError>;
+
+// How `train` splits and scores the targets of regressors (`f64`) and
+// classifiers (`usize`).
+trait TargetKind: Clone + 'static {
+    // Reported by cross-validation; the first is also reported after fitting.
+    const METRICS: &'static [(&'static str, Metric<Self>)];
+
+    fn folds(y: &[Self], k: usize, seed: u64) -> Result<Vec<Fold>, ModelSelectionError>;
+
+    // What gets recorded as `TrainingMetadata::final_loss`.
+    fn training_loss(
+        y: &[Self],
+        predictions: &[Self],
+        report: Option<&TrainingReport>,
+    ) -> Option<f64>;
+}
+
+impl TargetKind for f64 {
+    const METRICS: &'static [(&'static str, Metric<f64>)] =
+        &[("MSE", metrics::mse), ("R²", metrics::r2)];
+
+    fn folds(y: &[f64], k: usize, seed: u64) -> Result<Vec<Fold>, ModelSelectionError> {
+        model_selection::k_fold(y.len(), k, Some(seed))
+    }
+
+    fn training_loss(y: &[f64], predictions: &[f64], _: Option<&TrainingReport>) -> Option<f64> {
+        metrics::mse(y, predictions).ok()
+    }
+}
+
+impl TargetKind for usize {
+    const METRICS: &'static [(&'static str, Metric<usize>)] = &[("accuracy", metrics::accuracy)];
+
+    fn folds(y: &[usize], k: usize, seed: u64) -> Result<Vec<Fold>, ModelSelectionError> {
+        model_selection::stratified_k_fold(y, k, Some(seed))
+    }
+
+    fn training_loss(_: &[usize], _: &[usize], report: Option<&TrainingReport>) -> Option<f64> {
+        report.map(|report| report.final_loss)
+    }
+}
+
+// Class indices from a target column, which must hold whole numbers >= 0.
+fn class_labels(target: &[f64]) -> Result<Vec<usize>, Box<dyn Error>> {
+    target
+        .iter()
+        .map(|&value| {
+            if value >= 0.0 && value.fract() == 0.0 {
+                Ok(value as usize)
+            } else {
+                Err(format!("target value {} is not a class index", value).into())
+            }
+        })
+        .collect()
+}
+
+// Reads the columns `file` was trained on the way `train` read them: the
+// categories get the same codes, and missing values are kept if the model
+// imputes them.
+fn model_csv_options(args: &CsvArgs, target: Option<&str>, file: &ModelFile) -> CsvOptions {
+    let feature_names = &file.metadata.feature_names;
+    let mut options = csv_options(args, target).features(feature_names.iter().map(String::as_str));
+    let SavedModel::Pipeline(pipeline) = &file.model else {
+        return options;
+    };
+
+    // `train` puts the encoder before any step that moves columns around
+    for step in &pipeline.steps {
+        let (columns, names) = match step {
+            FeatureTransform::OneHot(encoder) => (&encoder.columns, &encoder.category_names),
+            FeatureTransform::Ordinal(encoder) => (&encoder.columns, &encoder.category_names),
+            FeatureTransform::Imputer(_) => {
+                if !args.drop_missing {
+                    options = options.missing(MissingValues::Keep);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        for (&j, names) in columns.iter().zip(names) {
+            options = options.categories(feature_names[j].as_str(), names.clone());
+        }
+    }
+    options
+}
+
+// Predicts on every row of `data`, naming the category when the file has one
+// the model wasn't trained on.
+fn predict_dataset(file: &ModelFile, data: &Dataset) -> Result<Vec<f64>, Box<dyn Error>> {
+    file.model.predict(&data.features).map_err(|err| {
+        if let ModelError::Preprocessing(PreprocessingError::UnknownCategory { column, value }) =
+            err
+            && let Some(name) = data.categories[column].get(value as usize)
+        {
+            return format!(
+                "column '{}' has category '{}' that the model was not trained on",
+                data.feature_names[column], name
+            )
+            .into();
+        }
+        err.into()
+    })
+}
+
+fn predict(args: &PredictArgs) -> Result<(), Box<dyn Error>> {
+    // Dropped rows would shift every later prediction onto the wrong row
+    if args.csv.drop_missing {
+        return Err(
+            "--drop-missing cannot be used with predict: the predictions would no \
+             longer line up with the input rows"
+                .into(),
+        );
+    }
+    let file = ModelFile::load(&args.model)?;
+    let options = model_csv_options(&args.csv, None, &file);
+    let data = load_dataset(&args.csv, &options)?;
+    let predictions = predict_dataset(&file, &data)?;
+
+    let mut out: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    writeln!(out, "{}", file.metadata.target_name)?;
+    for prediction in predictions {
+        writeln!(out, "{}", prediction)?;
+    }
+
+    Ok(())
+}
+
+fn eval(args: &EvalArgs) -> Result<(), Box<dyn Error>> {
+    let file = ModelFile::load(&args.model)?;
+    let target = args.target.as_deref().unwrap_or(&file.metadata.target_name);
+    let options = model_csv_options(&args.csv, Some(target), &file);
+    let data = load_dataset(&args.csv, &options)?;
+    let predictions = predict_dataset(&file, &data)?;
+    if file.model.is_classifier() {
+        return eval_classifier(&data.target, &predictions);
+    }
+
+    let y = &data.target;
+    println!("Samples: {}", data.n_samples());
+    println!("MSE: {:.5}", metrics::mse(y, &predictions)?);
+    println!("RMSE: {:.5}", metrics::rmse(y, &predictions)?);
+    println!("MAE: {:.5}", metrics::mae(y, &predictions)?);
+    println!(
+        "Median absolute error: {:.5}",
+        metrics::median_absolute_error(y, &predictions)?
+    );
+    println!("Max error: {:.5}", metrics::max_error(y, &predictions)?);
+    println!("MAPE: {:.2}%", 100.0 * metrics::mape(y, &predictions)?);
+    println!("R²: {:.5}", metrics::r2(y, &predictions)?);
+    // Undefined when there are too few samples for the number of features
+    if let Ok(adjusted) = metrics::adjusted_r2(y, &predictions, data.n_features()) {
+        println!("Adjusted R²: {:.5}", adjusted);
+    }
+    println!(
+        "Explained variance: {:.5}",
+        metrics::explained_variance(y, &predictions)?
+    );
+    println!(
+        "Within ±{}: {:.1}%",
+        args.tolerance,
+        100.0 * metrics::tolerance_accuracy(y, &predictions, args.tolerance)?
+    );
+
+    Ok(())
+}
+
+fn generate(args: &GenerateArgs, seed: u64) -> Result<(), Box<dyn Error>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n_features = args.features.unwrap_or(match args.kind {
+        DataKind::Regression => 1,
+        _ => 2,
+    });
+    let shape = match args.kind {
+        DataKind::Regression => return generate_regression(args, n_features, &mut rng),
+        DataKind::Blobs => Shape::Blobs {
+            n_classes: args.classes,
+            n_features,
+            cluster_std: args.cluster_std,
+        },
+        DataKind::Moons => Shape::Moons,
+        DataKind::Circles => Shape::Circles {
+            factor: args.factor,
+        },
+        DataKind::Xor => Shape::Xor,
+        DataKind::Separable => Shape::LinearlySeparable {
+            n_features,
+            margin: args.margin,
+        },
+    };
+
+    let mut generator = ClassificationGenerator::new(shape, args.samples).noise(args.feature_noise);
+    if let Some(weights) = &args.class_weights {
+        generator = generator.weights(weights.clone());
+    }
+    let data = generator.generate(&mut rng);
+    let rows = data
+        .features
+        .iter()
+        .zip(&data.labels)
+        .map(|(row, &label)| row.iter().copied().chain([label as f64]).collect());
+    write_csv(&args.out, shape.n_features(), "label", rows)?;
+
+    println!("Wrote {} rows to {}", data.labels.len(), args.out.display());
+    println!("Samples per class: {:?}", generator.class_counts());
+    for (class, center) in data.centers.iter().enumerate() {
+        println!("Center of class {}: {:?}", class, center);
+    }
+
+    Ok(())
+}
+
+fn generate_regression(
+    args: &GenerateArgs,
+    n_features: usize,
+    rng: &mut StdRng,
+) -> Result<(), Box<dyn Error>> {
+    let mut generator = RegressionGenerator::new(args.samples, n_features)
+        .feature_range(args.low, args.high)
+        .intercept(args.intercept)
+        .noise(args.noise.build(args.noise_level))
+        .outliers(args.outliers, args.outlier_magnitude);
+    if let Some(coefficients) = &args.coefficients {
+        if let Some(features) = args.features
+            && features != coefficients.len()
+        {
+            return Err(format!(
+                "--features {} does not match the {} values of --coefficients",
+                features,
+                coefficients.len()
+            )
+            .into());
+        }
+        generator = generator.coefficients(coefficients.clone());
+    }
+    if let Some(n_informative) = args.informative {
+        generator = generator.n_informative(n_informative);
+    }
+    let data = generator.generate(rng);
+    let rows = data
+        .features
+        .iter()
+        .zip(&data.target)
+        .map(|(row, &y)| row.iter().copied().chain([y]).collect());
+    write_csv(&args.out, generator.n_features, "y", rows)?;
+
+    println!("Wrote {} rows to {}", data.target.len(), args.out.display());
+    println!("True coefficients: {:?}", data.coefficients);
+    println!("True intercept: {}", data.intercept);
+    if !data.outliers.is_empty() {
+        println!("Outlier rows: {}", data.outliers.len());
+    }
+
+    Ok(())
+}
+
+// Writes feature columns `x0, x1, ...` followed by the `target` column.
+fn write_csv(
+    path: &Path,
+    n_features: usize,
+    target: &str,
+    rows: impl Iterator<Item = Vec<f64>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut out = BufWriter::new(File::create(path)?);
+    let header: Vec<String> = (0..n_features)
+        .map(|j| format!("x{}", j))
+        .chain([target.to_string()])
+        .collect();
+    writeln!(out, "{}", header.join(","))?;
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(f64::to_string).collect();
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn eval_classifier(target: &[f64], predictions: &[f64]) -> Result<(), Box<dyn Error>> {
+    let y = class_labels(target)?;
+    let predicted: Vec<usize> = predictions.iter().map(|&c| c as usize).collect();
+    let matrix = metrics::ConfusionMatrix::new(&y, &predicted)?;
+
+    println!("Samples: {}", y.len());
+    println!("Accuracy: {:.5}", matrix.accuracy());
+    println!(
+        "Macro precision: {:.5}",
+        matrix.average_precision(Average::Macro)
+    );
+    println!("Macro recall: {:.5}", matrix.average_recall(Average::Macro));
+    println!("Macro F1: {:.5}", matrix.average_f1(Average::Macro));
+    println!("Confusion matrix (rows: true class, columns: predicted):");
+    print!("{}", matrix);
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Some(Command::Train(args)) => train(args, cli.seed),
+        Some(Command::Predict(args)) => predict(args),
+        Some(Command::Eval(args)) => eval(args),
+        Some(Command::Generate(args)) => generate(args, cli.seed),
+        Some(Command::Demo) | None => demo(cli.seed),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn demo(seed: u64) -> Result<(), Box<dyn Error>> {
+    let mut rng = StdRng::seed_from_u64(seed);
 
-fn main() {
     // Generate dataset
-    let data_x: Vec<f64> = datagen::generate_100_samples();
+    let data_x: Vec<f64> = datagen::generate_100_samples_with_rng(&mut rng);
     let data_y: Vec<f64> = datagen::gen_y(data_x.clone());
 
     // Initialize model
-    let mut model = simple_li_reg_model::LinRegModel::new();
+    let mut model = LinRegModel::with_rng(&mut rng);
 
     // Before training: compute initial MSE
-    let initial_loss = simple_li_reg_model::mse(&data_y, &model.predict(&data_x));
+    let initial_loss = metrics::mse(&data_y, &model.predict(&data_x))?;
     println!("Initial MSE: {:.5}", initial_loss);
 
     // Train model
-    model.train(&data_x, &data_y, 0.01, 100000);
+    let report = model.train(&data_x, &data_y, 0.01, 100000)?;
+    println!(
+        "Stopped after {} epochs: {}",
+        report.epochs_run, report.stop_reason
+    );
 
     // After training: compute final MSE
-    let final_loss = simple_li_reg_model::mse(&data_y, &model.predict(&data_x));
+    let final_loss = metrics::mse(&data_y, &model.predict(&data_x))?;
     println!("Final MSE: {:.5}", final_loss);
 
     println!("Trained parameters: w = {:.5}, b = {:.5}", model.w, model.b);
 
+    // Compare against the exact normal-equation solution
+    match LinRegModel::fit_closed_form(&data_x, &data_y) {
+        Ok(exact) => println!(
+            "Closed-form parameters: w = {:.5}, b = {:.5} (|Δw| = {:.2e}, |Δb| = {:.2e})",
+            exact.w,
+            exact.b,
+            (exact.w - model.w).abs(),
+            (exact.b - model.b).abs()
+        ),
+        Err(e) => println!("Closed-form Error: {}", e),
+    }
+
     // Check how many predictions are close to the real values
-    let correct_predictions = simple_li_reg_model::test_model(&data_y, &model.predict(&data_x));
-    println!("Numbers of close predictions: {}", correct_predictions);
+    let close_predictions = metrics::tolerance_accuracy(&data_y, &model.predict(&data_x), 0.1)?;
+    println!(
+        "Share of close predictions: {:.1}%",
+        100.0 * close_predictions
+    );
 
     // Visualize predictions vs actual values
     let predictions = model.predict(&data_x);
-    visualization::plot_predictions_vs_actual(&data_x, &data_y, &predictions);
+    if let Err(e) = visualization::plot_predictions_vs_actual(&data_x, &data_y, &predictions) {
+        println!("Plotting Error: {}", e);
+    }
+
+    // Multivariate model: y = 2·x1 - 1·x2 + 3, with x2 built from a second sample
+    let data_x2: Vec<f64> = datagen::generate_100_samples_with_rng(&mut rng);
+    let features: Vec<Vec<f64>> = data_x
+        .iter()
+        .zip(data_x2.iter())
+        .map(|(&x1, &x2)| vec![x1, x2])
+        .collect();
+    let targets: Vec<f64> = features.iter().map(|f| 2.0 * f[0] - f[1] + 3.0).collect();
+
+    let mut multi_model = MultiLinRegModel::with_rng(2, &mut rng);
+    match multi_model
+        .train(&features, &targets, 0.01, 100000)
+        .and_then(|_| Ok(multi_model.predict(&features)?))
+    {
+        Ok(multi_predictions) => println!(
+            "Multivariate parameters: w = {:.5?}, b = {:.5}, MSE = {:.5}",
+            multi_model.w,
+            multi_model.b,
+            metrics::mse(&targets, &multi_predictions)?
+        ),
+        Err(e) => println!("Multivariate Training Error: {}", e),
+    }
+
+    // Polynomial model: y = 0.5·x² - 2·x + 1
+    let curve_x: Vec<Vec<f64>> = data_x.iter().map(|&x| vec![x]).collect();
+    let curve_y: Vec<f64> = data_x
+        .iter()
+        .map(|&x| 0.5 * x * x - 2.0 * x + 1.0)
+        .collect();
+
+    let poly_fit = PolyRegModel::fit_closed_form(&curve_x, &curve_y, PolynomialFeatures::new(2))
+        .and_then(|poly_model| Ok((poly_model.predict(&curve_x)?, poly_model)));
+    match poly_fit {
+        Ok((poly_predictions, poly_model)) => {
+            println!(
+                "Polynomial parameters: w = {:.5?}, b = {:.5}, MSE = {:.5}",
+                poly_model.linear.w,
+                poly_model.linear.b,
+                metrics::mse(&curve_y, &poly_predictions)?
+            );
+            if let Err(e) = visualization::plot_predictions_vs_actual_to(
+                &data_x,
+                &curve_y,
+                &poly_predictions,
+                "polynomial_fit.png",
+            ) {
+                println!("Plotting Error: {}", e);
+            }
+        }
+        Err(e) => println!("Polynomial Fitting Error: {}", e),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_seconds(value: &str) -> Result<Option<f64>, clap::Error> {
+        let cli = Cli::try_parse_from([
+            "mlr",
+            "train",
+            "--data=d.csv",
+            "--target=y",
+            &format!("--max-seconds={}", value),
+        ])?;
+        match cli.command {
+            Some(Command::Train(args)) => Ok(args.max_seconds),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn max_seconds_must_be_a_positive_duration() {
+        assert_eq!(max_seconds("0.5").unwrap(), Some(0.5));
+        for value in ["-1", "0", "1e30", "inf", "NaN", "soon"] {
+            assert!(max_seconds(value).is_err(), "{} was accepted", value);
+        }
+    }
 }
@@ -0,0 +1,475 @@
+use std::fmt;
+
+use crate::error::MetricsError;
+use crate::metrics::check_lengths;
+
+/// How per-class scores are combined into one number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Average {
+    /// The score of class `1` alone, for binary problems.
+    Binary,
+    /// Unweighted mean over classes, so rare classes count as much as
+    /// common ones.
+    Macro,
+    /// Computed from the counts pooled over all classes. For single-label
+    /// data micro precision, recall and F1 all equal the accuracy.
+    Micro,
+    /// Mean over classes weighted by how many true samples each has.
+    Weighted,
+}
+
+/// Counts of (true class, predicted class) pairs: `counts[t][p]` samples of
+/// class `t` were predicted as `p`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl ConfusionMatrix {
+    /// Tallies the labels, with as many classes as the largest label needs.
+    pub fn new(y_true: &[usize], y_pred: &[usize]) -> Result<Self, MetricsError> {
+        check_lengths(y_true, y_pred)?;
+        let n_classes = y_true.iter().chain(y_pred).max().map_or(0, |&max| max + 1);
+        Self::with_classes(y_true, y_pred, n_classes)
+    }
+
+    /// Tallies the labels into a fixed number of classes, so classes that
+    /// never occur still get a row and column.
+    pub fn with_classes(
+        y_true: &[usize],
+        y_pred: &[usize],
+        n_classes: usize,
+    ) -> Result<Self, MetricsError> {
+        check_lengths(y_true, y_pred)?;
+        if let Some(&label) = y_true.iter().chain(y_pred).find(|&&l| l >= n_classes) {
+            return Err(MetricsError::LabelOutOfRange { label, n_classes });
+        }
+
+        let mut counts = vec![vec![0; n_classes]; n_classes];
+        for (&t, &p) in y_true.iter().zip(y_pred) {
+            counts[t][p] += 1;
+        }
+        Ok(Self { counts })
+    }
+
+    pub fn n_classes(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Number of samples tallied.
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    pub fn true_positives(&self, class: usize) -> usize {
+        self.counts[class][class]
+    }
+
+    pub fn false_positives(&self, class: usize) -> usize {
+        self.predicted(class) - self.true_positives(class)
+    }
+
+    pub fn false_negatives(&self, class: usize) -> usize {
+        self.support(class) - self.true_positives(class)
+    }
+
+    /// Number of samples whose true class is `class`.
+    pub fn support(&self, class: usize) -> usize {
+        self.counts[class].iter().sum()
+    }
+
+    /// Number of samples predicted as `class`.
+    pub fn predicted(&self, class: usize) -> usize {
+        self.counts.iter().map(|row| row[class]).sum()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        let correct: usize = (0..self.n_classes()).map(|c| self.true_positives(c)).sum();
+        ratio(correct, self.total())
+    }
+
+    /// Share of the samples predicted as `class` that really are; 0 if none
+    /// were predicted as `class`.
+    pub fn precision(&self, class: usize) -> f64 {
+        ratio(self.true_positives(class), self.predicted(class))
+    }
+
+    /// Share of the samples of `class` that were found; 0 if there are none.
+    pub fn recall(&self, class: usize) -> f64 {
+        ratio(self.true_positives(class), self.support(class))
+    }
+
+    /// Harmonic mean of precision and recall for `class`.
+    pub fn f1(&self, class: usize) -> f64 {
+        let (p, r) = (self.precision(class), self.recall(class));
+        if p + r == 0.0 {
+            0.0
+        } else {
+            2.0 * p * r / (p + r)
+        }
+    }
+
+    /// Precision averaged over classes.
+    pub fn average_precision(&self, average: Average) -> f64 {
+        self.average(average, Self::precision)
+    }
+
+    /// Recall averaged over classes.
+    pub fn average_recall(&self, average: Average) -> f64 {
+        self.average(average, Self::recall)
+    }
+
+    /// F1 averaged over classes.
+    pub fn average_f1(&self, average: Average) -> f64 {
+        self.average(average, Self::f1)
+    }
+
+    fn average(&self, average: Average, score: fn(&Self, usize) -> f64) -> f64 {
+        let k = self.n_classes();
+        match average {
+            Average::Binary if k > 1 => score(self, 1),
+            Average::Binary => 0.0,
+            Average::Macro => (0..k).map(|c| score(self, c)).sum::<f64>() / k.max(1) as f64,
+            // Pooled counts: every false positive is some other class's
+            // false negative, so all three scores reduce to the accuracy
+            Average::Micro => self.accuracy(),
+            Average::Weighted => {
+                let weighted: f64 = (0..k)
+                    .map(|c| score(self, c) * self.support(c) as f64)
+                    .sum();
+                weighted / self.total().max(1) as f64
+            }
+        }
+    }
+}
+
+/// A table with true classes down the side and predicted classes across.
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .counts
+            .iter()
+            .flatten()
+            .max()
+            .map_or(1, |max| max.to_string().len())
+            .max(self.n_classes().to_string().len())
+            .max(4);
+
+        write!(f, "{:>w$} |", "t\\p", w = width)?;
+        for p in 0..self.n_classes() {
+            write!(f, " {:>w$}", p, w = width)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{}",
+            "-".repeat((width + 1) * (self.n_classes() + 1) + 1)
+        )?;
+
+        for (t, row) in self.counts.iter().enumerate() {
+            write!(f, "{:>w$} |", t, w = width)?;
+            for count in row {
+                write!(f, " {:>w$}", count, w = width)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Share of predictions that match the true label.
+pub fn accuracy(y_true: &[usize], y_pred: &[usize]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    let correct = y_true.iter().zip(y_pred).filter(|(t, p)| t == p).count();
+    Ok(ratio(correct, y_true.len()))
+}
+
+pub fn precision(
+    y_true: &[usize],
+    y_pred: &[usize],
+    average: Average,
+) -> Result<f64, MetricsError> {
+    Ok(ConfusionMatrix::new(y_true, y_pred)?.average_precision(average))
+}
+
+pub fn recall(y_true: &[usize], y_pred: &[usize], average: Average) -> Result<f64, MetricsError> {
+    Ok(ConfusionMatrix::new(y_true, y_pred)?.average_recall(average))
+}
+
+pub fn f1_score(y_true: &[usize], y_pred: &[usize], average: Average) -> Result<f64, MetricsError> {
+    Ok(ConfusionMatrix::new(y_true, y_pred)?.average_f1(average))
+}
+
+/// Receiver operating characteristic of a binary classifier: the true and
+/// false positive rates obtained by predicting class `1` whenever the score
+/// is at least each threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RocCurve {
+    pub fpr: Vec<f64>,
+    pub tpr: Vec<f64>,
+    /// Decreasing thresholds; the first, `+∞`, predicts nothing positive.
+    pub thresholds: Vec<f64>,
+}
+
+impl RocCurve {
+    /// Area under the curve by the trapezoidal rule: the probability that a
+    /// random positive sample scores higher than a random negative one.
+    pub fn auc(&self) -> f64 {
+        self.fpr
+            .windows(2)
+            .zip(self.tpr.windows(2))
+            .map(|(x, y)| (x[1] - x[0]) * (y[0] + y[1]) / 2.0)
+            .sum()
+    }
+}
+
+/// ROC curve for binary labels (`0`/`1`) and scores where higher means
+/// more likely class `1`, e.g. `LogisticRegression::predict_proba`.
+pub fn roc_curve(y_true: &[usize], scores: &[f64]) -> Result<RocCurve, MetricsError> {
+    check_lengths(y_true, scores)?;
+    if let Some(&label) = y_true.iter().find(|&&l| l > 1) {
+        return Err(MetricsError::LabelOutOfRange {
+            label,
+            n_classes: 2,
+        });
+    }
+    let positives = y_true.iter().filter(|&&l| l == 1).count();
+    let negatives = y_true.len() - positives;
+    if positives == 0 || negatives == 0 {
+        return Err(MetricsError::SingleClass);
+    }
+
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+    let mut curve = RocCurve {
+        fpr: vec![0.0],
+        tpr: vec![0.0],
+        thresholds: vec![f64::INFINITY],
+    };
+    let (mut tp, mut fp) = (0, 0);
+    for (k, &i) in order.iter().enumerate() {
+        if y_true[i] == 1 {
+            tp += 1;
+        } else {
+            fp += 1;
+        }
+        // Samples with tied scores share a single point
+        let next_differs = order
+            .get(k + 1)
+            .is_none_or(|&next| scores[next] != scores[i]);
+        if next_differs {
+            curve.fpr.push(ratio(fp, negatives));
+            curve.tpr.push(ratio(tp, positives));
+            curve.thresholds.push(scores[i]);
+        }
+    }
+    Ok(curve)
+}
+
+/// Area under the ROC curve. See `roc_curve`.
+pub fn roc_auc(y_true: &[usize], scores: &[f64]) -> Result<f64, MetricsError> {
+    Ok(roc_curve(y_true, scores)?.auc())
+}
+
+// Probabilities are clipped this far from 0 and 1 so a confident mistake
+// costs a large but finite loss.
+const LOG_LOSS_EPSILON: f64 = 1e-15;
+
+/// Cross-entropy of predicted class probabilities, `-Σ ln p(true class) / n`,
+/// with `probabilities[i][c]` the probability of class `c` for sample `i`
+/// (e.g. `SoftmaxRegression::predict_proba`).
+pub fn log_loss(y_true: &[usize], probabilities: &[Vec<f64>]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, probabilities)?;
+    let mut total = 0.0;
+    for (&label, p) in y_true.iter().zip(probabilities) {
+        let p_true = p.get(label).ok_or(MetricsError::LabelOutOfRange {
+            label,
+            n_classes: p.len(),
+        })?;
+        total -= p_true.clamp(LOG_LOSS_EPSILON, 1.0 - LOG_LOSS_EPSILON).ln();
+    }
+    Ok(total / y_true.len() as f64)
+}
+
+/// `log_loss` for binary labels given the probability of class `1` only
+/// (e.g. `LogisticRegression::predict_proba`).
+pub fn binary_log_loss(y_true: &[usize], p_positive: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, p_positive)?;
+    let probabilities: Vec<Vec<f64>> = p_positive.iter().map(|&p| vec![1.0 - p, p]).collect();
+    log_loss(y_true, &probabilities)
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Class 2 is never predicted: its precision is 0/0
+    const Y_TRUE: [usize; 6] = [0, 0, 1, 1, 2, 2];
+    const Y_PRED: [usize; 6] = [0, 1, 1, 1, 0, 0];
+
+    fn assert_close(found: f64, expected: f64) {
+        assert!(
+            (found - expected).abs() < 1e-12,
+            "{} != {}",
+            found,
+            expected
+        );
+    }
+
+    #[test]
+    fn confusion_matrix_counts_true_against_predicted() {
+        let matrix = ConfusionMatrix::new(&Y_TRUE, &Y_PRED).unwrap();
+        assert_eq!(matrix.counts, [[1, 1, 0], [0, 2, 0], [2, 0, 0]]);
+        assert_eq!(matrix.total(), 6);
+        assert_eq!(matrix.true_positives(1), 2);
+        assert_eq!(matrix.false_positives(0), 2);
+        assert_eq!(matrix.false_negatives(2), 2);
+        assert_eq!(matrix.support(2), 2);
+        assert_eq!(matrix.predicted(2), 0);
+        assert_eq!(matrix.accuracy(), 0.5);
+
+        let padded = ConfusionMatrix::with_classes(&[0, 1], &[0, 0], 3).unwrap();
+        assert_eq!(padded.counts, [[1, 0, 0], [1, 0, 0], [0, 0, 0]]);
+        assert_eq!(
+            ConfusionMatrix::with_classes(&[0, 3], &[0, 0], 3),
+            Err(MetricsError::LabelOutOfRange {
+                label: 3,
+                n_classes: 3
+            })
+        );
+
+        let table = ConfusionMatrix::new(&[0, 1], &[1, 1]).unwrap().to_string();
+        assert_eq!(
+            table.lines().collect::<Vec<_>>(),
+            [
+                " t\\p |    0    1",
+                "----------------",
+                "   0 |    0    1",
+                "   1 |    0    1"
+            ]
+        );
+    }
+
+    #[test]
+    fn per_class_scores() {
+        let matrix = ConfusionMatrix::new(&Y_TRUE, &Y_PRED).unwrap();
+        assert_close(matrix.precision(0), 1.0 / 3.0);
+        assert_close(matrix.recall(0), 0.5);
+        assert_close(matrix.f1(0), 0.4);
+        assert_close(matrix.precision(1), 2.0 / 3.0);
+        assert_close(matrix.recall(1), 1.0);
+        assert_close(matrix.f1(1), 0.8);
+    }
+
+    #[test]
+    fn a_class_never_predicted_scores_zero() {
+        let matrix = ConfusionMatrix::new(&Y_TRUE, &Y_PRED).unwrap();
+        assert_eq!(matrix.precision(2), 0.0);
+        assert_eq!(matrix.recall(2), 0.0);
+        assert_eq!(matrix.f1(2), 0.0);
+
+        // No positives predicted at all, and none present at all
+        assert_eq!(precision(&[0, 1], &[0, 0], Average::Binary), Ok(0.0));
+        assert_eq!(recall(&[0, 0], &[0, 1], Average::Binary), Ok(0.0));
+        assert_eq!(f1_score(&[0, 1], &[0, 0], Average::Binary), Ok(0.0));
+    }
+
+    #[test]
+    fn averages_over_classes() {
+        assert_eq!(
+            precision(&[0, 1, 1, 0], &[0, 1, 0, 0], Average::Binary),
+            Ok(1.0)
+        );
+        assert_eq!(
+            recall(&[0, 1, 1, 0], &[0, 1, 0, 0], Average::Binary),
+            Ok(0.5)
+        );
+
+        type Metric = fn(&[usize], &[usize], Average) -> Result<f64, MetricsError>;
+        let score = |metric: Metric, average| metric(&Y_TRUE, &Y_PRED, average).unwrap();
+        assert_close(score(precision, Average::Macro), 1.0 / 3.0);
+        assert_close(score(recall, Average::Macro), 0.5);
+        assert_close(score(f1_score, Average::Macro), 0.4);
+        assert_close(
+            score(f1_score, Average::Weighted),
+            (0.4 * 2.0 + 0.8 * 2.0) / 6.0,
+        );
+        for metric in [precision, recall, f1_score] {
+            assert_eq!(
+                score(metric, Average::Micro),
+                accuracy(&Y_TRUE, &Y_PRED).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn mismatched_or_empty_labels_are_errors() {
+        let mismatch = Err(MetricsError::LengthMismatch {
+            y_true: 6,
+            y_pred: 5,
+        });
+        assert_eq!(accuracy(&Y_TRUE, &Y_PRED[..5]), mismatch);
+        assert_eq!(precision(&Y_TRUE, &Y_PRED[..5], Average::Macro), mismatch);
+        assert_eq!(recall(&Y_TRUE, &Y_PRED[..5], Average::Macro), mismatch);
+        assert_eq!(f1_score(&Y_TRUE, &Y_PRED[..5], Average::Macro), mismatch);
+        assert_eq!(
+            ConfusionMatrix::new(&Y_TRUE, &Y_PRED[..5]),
+            Err(MetricsError::LengthMismatch {
+                y_true: 6,
+                y_pred: 5
+            })
+        );
+        assert_eq!(accuracy(&[], &[]), Err(MetricsError::Empty));
+        assert_eq!(
+            roc_auc(&[0, 1], &[0.5]),
+            Err(MetricsError::LengthMismatch {
+                y_true: 2,
+                y_pred: 1
+            })
+        );
+    }
+
+    #[test]
+    fn roc_curve_and_auc() {
+        let curve = roc_curve(&[0, 0, 1, 1], &[0.1, 0.4, 0.35, 0.8]).unwrap();
+        assert_eq!(curve.thresholds, [f64::INFINITY, 0.8, 0.4, 0.35, 0.1]);
+        assert_eq!(curve.tpr, [0.0, 0.5, 0.5, 1.0, 1.0]);
+        assert_eq!(curve.fpr, [0.0, 0.0, 0.5, 0.5, 1.0]);
+        assert_eq!(curve.auc(), 0.75);
+
+        assert_eq!(roc_auc(&[0, 1], &[0.2, 0.9]), Ok(1.0));
+        // Tied scores share one point, halfway between right and wrong
+        assert_eq!(roc_auc(&[0, 1], &[0.5, 0.5]), Ok(0.5));
+        assert_eq!(
+            roc_auc(&[1, 1], &[0.2, 0.9]),
+            Err(MetricsError::SingleClass)
+        );
+    }
+
+    #[test]
+    fn log_loss_clips_confident_mistakes() {
+        let loss = log_loss(&[0, 1], &[vec![0.5, 0.5], vec![0.2, 0.8]]).unwrap();
+        assert_close(loss, -(0.5f64.ln() + 0.8f64.ln()) / 2.0);
+        assert_close(binary_log_loss(&[0, 1], &[0.5, 0.8]).unwrap(), loss);
+
+        let wrong = binary_log_loss(&[1], &[0.0]).unwrap();
+        assert!(wrong.is_finite());
+        assert_close(wrong, -LOG_LOSS_EPSILON.ln());
+        assert_eq!(
+            log_loss(&[2], &[vec![0.5, 0.5]]),
+            Err(MetricsError::LabelOutOfRange {
+                label: 2,
+                n_classes: 2
+            })
+        );
+    }
+}
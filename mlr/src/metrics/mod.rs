@@ -0,0 +1,32 @@
+//! Scores comparing a model's predictions with the true values.
+//!
+//! Every metric checks that both inputs have the same, non-zero length
+//! and returns `MetricsError` otherwise.
+
+pub mod classification;
+pub mod regression;
+
+pub use classification::{
+    Average, ConfusionMatrix, RocCurve, accuracy, binary_log_loss, f1_score, log_loss, precision,
+    recall, roc_auc, roc_curve,
+};
+pub use regression::{
+    adjusted_r2, explained_variance, mae, mape, max_error, median_absolute_error, mse, r2, rmse,
+    tolerance_accuracy,
+};
+
+use crate::error::MetricsError;
+
+// Checks that `y_true` and `y_pred` pair up one-to-one and aren't empty.
+fn check_lengths<T, U>(y_true: &[T], y_pred: &[U]) -> Result<(), MetricsError> {
+    if y_true.len() != y_pred.len() {
+        return Err(MetricsError::LengthMismatch {
+            y_true: y_true.len(),
+            y_pred: y_pred.len(),
+        });
+    }
+    if y_true.is_empty() {
+        return Err(MetricsError::Empty);
+    }
+    Ok(())
+}
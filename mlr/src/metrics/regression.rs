@@ -0,0 +1,245 @@
+use crate::error::MetricsError;
+use crate::metrics::check_lengths;
+
+/// Mean squared error, `Σ (y - ŷ)² / n`.
+pub fn mse(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    Ok(mean(errors(y_true, y_pred).map(|e| e * e)))
+}
+
+/// Root mean squared error, in the units of the target.
+pub fn rmse(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    Ok(mse(y_true, y_pred)?.sqrt())
+}
+
+/// Mean absolute error, `Σ |y - ŷ| / n`.
+pub fn mae(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    Ok(mean(errors(y_true, y_pred).map(f64::abs)))
+}
+
+/// Coefficient of determination, `1 - SS_res / SS_tot`: 1 for a perfect
+/// fit, 0 for always predicting the mean, negative for anything worse.
+///
+/// A constant `y_true` has no variance to explain; the score is then 1 for
+/// a perfect fit and 0 otherwise.
+pub fn r2(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    let y_mean = mean(y_true.iter().copied());
+    let ss_res: f64 = errors(y_true, y_pred).map(|e| e * e).sum();
+    let ss_tot: f64 = y_true.iter().map(|y| (y - y_mean).powi(2)).sum();
+
+    Ok(score_from_ratio(ss_res, ss_tot))
+}
+
+/// R² corrected for the number of features the model used,
+/// `1 - (1 - R²)·(n - 1) / (n - p - 1)`, so adding useless features
+/// doesn't raise the score.
+///
+/// Needs more samples than `n_features + 1`.
+pub fn adjusted_r2(y_true: &[f64], y_pred: &[f64], n_features: usize) -> Result<f64, MetricsError> {
+    let r2 = r2(y_true, y_pred)?;
+    let n = y_true.len();
+    if n <= n_features + 1 {
+        return Err(MetricsError::TooFewSamples {
+            n_samples: n,
+            n_features,
+        });
+    }
+
+    let n = n as f64;
+    Ok(1.0 - (1.0 - r2) * (n - 1.0) / (n - n_features as f64 - 1.0))
+}
+
+/// Mean absolute percentage error, `Σ |y - ŷ| / |y| / n`, as a fraction
+/// (0.05 is 5%). A zero in `y_true` is replaced by `f64::EPSILON`, so it
+/// yields a huge error instead of dividing by zero.
+pub fn mape(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    Ok(mean(
+        y_true
+            .iter()
+            .zip(y_pred)
+            .map(|(y, p)| (y - p).abs() / y.abs().max(f64::EPSILON)),
+    ))
+}
+
+/// Median of `|y - ŷ|`, robust to a few very bad predictions.
+pub fn median_absolute_error(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    let mut abs_errors: Vec<f64> = errors(y_true, y_pred).map(f64::abs).collect();
+    abs_errors.sort_by(f64::total_cmp);
+
+    let mid = abs_errors.len() / 2;
+    Ok(if abs_errors.len().is_multiple_of(2) {
+        (abs_errors[mid - 1] + abs_errors[mid]) / 2.0
+    } else {
+        abs_errors[mid]
+    })
+}
+
+/// Explained variance, `1 - Var(y - ŷ) / Var(y)`. Unlike R² it ignores a
+/// constant offset in the predictions.
+///
+/// A constant `y_true` scores 1 if the errors are constant too, 0 otherwise.
+pub fn explained_variance(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    let residuals: Vec<f64> = errors(y_true, y_pred).collect();
+
+    Ok(score_from_ratio(variance(&residuals), variance(y_true)))
+}
+
+/// Largest `|y - ŷ|`: the worst single prediction.
+pub fn max_error(y_true: &[f64], y_pred: &[f64]) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    Ok(errors(y_true, y_pred).map(f64::abs).fold(0.0, f64::max))
+}
+
+/// Fraction of predictions within `tolerance` of the true value
+/// (`|y - ŷ| < tolerance`).
+pub fn tolerance_accuracy(
+    y_true: &[f64],
+    y_pred: &[f64],
+    tolerance: f64,
+) -> Result<f64, MetricsError> {
+    check_lengths(y_true, y_pred)?;
+    let close = errors(y_true, y_pred)
+        .filter(|e| e.abs() < tolerance)
+        .count();
+
+    Ok(close as f64 / y_true.len() as f64)
+}
+
+fn errors<'a>(y_true: &'a [f64], y_pred: &'a [f64]) -> impl ExactSizeIterator<Item = f64> + 'a {
+    y_true.iter().zip(y_pred).map(|(y, p)| y - p)
+}
+
+fn mean(values: impl ExactSizeIterator<Item = f64>) -> f64 {
+    let n = values.len() as f64;
+    values.sum::<f64>() / n
+}
+
+fn variance(values: &[f64]) -> f64 {
+    let mean = mean(values.iter().copied());
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+}
+
+// `1 - unexplained / total`, with the convention for a target that has no
+// variance at all.
+fn score_from_ratio(unexplained: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        1.0 - unexplained / total
+    } else if unexplained == 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Errors y - ŷ = [-0.5, 0, 0.5, -0.5]; y has mean 2.5 and SS_tot = 5
+    const Y: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+    const PRED: [f64; 4] = [1.5, 2.0, 2.5, 4.5];
+
+    fn assert_close(found: f64, expected: f64) {
+        assert!(
+            (found - expected).abs() < 1e-12,
+            "{} != {}",
+            found,
+            expected
+        );
+    }
+
+    #[test]
+    fn error_metrics() {
+        assert_close(mse(&Y, &PRED).unwrap(), 0.1875);
+        assert_close(rmse(&Y, &PRED).unwrap(), 0.1875f64.sqrt());
+        assert_close(mae(&Y, &PRED).unwrap(), 0.375);
+        assert_close(median_absolute_error(&Y, &PRED).unwrap(), 0.5);
+        assert_close(max_error(&Y, &PRED).unwrap(), 0.5);
+        assert_close(tolerance_accuracy(&Y, &PRED, 0.1).unwrap(), 0.25);
+    }
+
+    #[test]
+    fn r2_and_adjusted_r2() {
+        // 1 - 0.75 / 5
+        assert_close(r2(&Y, &PRED).unwrap(), 0.85);
+        assert_close(r2(&Y, &Y).unwrap(), 1.0);
+        assert_close(r2(&Y, &[2.5; 4]).unwrap(), 0.0);
+        assert!(r2(&Y, &[4.0, 3.0, 2.0, 1.0]).unwrap() < 0.0);
+
+        // 1 - 0.15 · (n - 1) / (n - p - 1)
+        assert_close(adjusted_r2(&Y, &PRED, 1).unwrap(), 1.0 - 0.15 * 3.0 / 2.0);
+        assert_close(adjusted_r2(&Y, &PRED, 2).unwrap(), 1.0 - 0.15 * 3.0);
+        assert_eq!(
+            adjusted_r2(&Y, &PRED, 3),
+            Err(MetricsError::TooFewSamples {
+                n_samples: 4,
+                n_features: 3
+            })
+        );
+    }
+
+    #[test]
+    fn constant_targets_have_nothing_to_explain() {
+        let y = [2.0; 3];
+        assert_eq!(r2(&y, &y).unwrap(), 1.0);
+        assert_eq!(r2(&y, &[2.0, 2.0, 3.0]).unwrap(), 0.0);
+        assert_eq!(explained_variance(&y, &[3.0; 3]).unwrap(), 1.0);
+        assert_eq!(explained_variance(&y, &[2.0, 2.0, 3.0]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn explained_variance_ignores_a_constant_offset() {
+        // Var(y - ŷ) = 0.171875 and Var(y) = 1.25
+        assert_close(
+            explained_variance(&Y, &PRED).unwrap(),
+            1.0 - 0.171875 / 1.25,
+        );
+
+        let shifted: Vec<f64> = Y.iter().map(|y| y + 1.0).collect();
+        assert_close(explained_variance(&Y, &shifted).unwrap(), 1.0);
+        assert_close(r2(&Y, &shifted).unwrap(), 0.2);
+    }
+
+    #[test]
+    fn mape_survives_a_zero_target() {
+        // (0.5/1 + 0 + 0.5/3 + 0.5/4) / 4
+        assert_close(mape(&Y, &PRED).unwrap(), (0.5 + 0.5 / 3.0 + 0.125) / 4.0);
+
+        let error = mape(&[0.0, 2.0], &[1.0, 2.0]).unwrap();
+        assert!(error.is_finite());
+        assert_eq!(error, 1.0 / f64::EPSILON / 2.0);
+        assert_eq!(mape(&[0.0], &[0.0]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn empty_and_mismatched_inputs_are_errors() {
+        type Metric = fn(&[f64], &[f64]) -> Result<f64, MetricsError>;
+        let metrics: [Metric; 8] = [
+            mse,
+            rmse,
+            mae,
+            r2,
+            mape,
+            median_absolute_error,
+            explained_variance,
+            max_error,
+        ];
+        for metric in metrics {
+            assert_eq!(metric(&[], &[]), Err(MetricsError::Empty));
+            assert_eq!(
+                metric(&Y, &PRED[..3]),
+                Err(MetricsError::LengthMismatch {
+                    y_true: 4,
+                    y_pred: 3
+                })
+            );
+        }
+        assert_eq!(adjusted_r2(&[], &[], 0), Err(MetricsError::Empty));
+        assert_eq!(tolerance_accuracy(&[], &[], 0.1), Err(MetricsError::Empty));
+    }
+}
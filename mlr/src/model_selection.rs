@@ -0,0 +1,391 @@
+//! Estimating how well a model generalizes by scoring it on data it wasn't
+//! fitted to.
+//!
+//! [`train_test_split`] holds out one test set. The fold generators
+//! ([`k_fold`], [`stratified_k_fold`] and [`leave_one_out`]) return index
+//! [`Fold`]s that [`cross_validate`] fits and scores a model on in turn;
+//! [`cross_validate_multi`] scores every fit with several metrics.
+//! Everything that shuffles takes a seed, so a split can be reproduced.
+
+use std::collections::BTreeMap;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::error::{MetricsError, ModelSelectionError};
+use crate::models::Fit;
+
+/// Rows and targets split into a training and a test set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainTestSplit<T> {
+    pub x_train: Vec<Vec<f64>>,
+    pub y_train: Vec<T>,
+    pub x_test: Vec<Vec<f64>>,
+    pub y_test: Vec<T>,
+}
+
+/// Shuffles the samples with `seed` and holds out `test_fraction` of them,
+/// rounded up, as the test set. Both sets get at least one sample.
+pub fn train_test_split<T: Clone>(
+    x: &[Vec<f64>],
+    y: &[T],
+    test_fraction: f64,
+    seed: u64,
+) -> Result<TrainTestSplit<T>, ModelSelectionError> {
+    check_lengths(x.len(), y.len())?;
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(ModelSelectionError::InvalidFraction(test_fraction));
+    }
+    let n = x.len();
+    if n < 2 {
+        return Err(ModelSelectionError::TooFewSamples {
+            n_samples: n,
+            n_splits: 2,
+        });
+    }
+
+    let mut indices: Vec<usize> = (0..n).collect();
+    indices.shuffle(&mut StdRng::seed_from_u64(seed));
+    let n_test = ((n as f64 * test_fraction).ceil() as usize).clamp(1, n - 1);
+    let (test, train) = indices.split_at(n_test);
+
+    Ok(TrainTestSplit {
+        x_train: select(x, train),
+        y_train: select(y, train),
+        x_test: select(x, test),
+        y_test: select(y, test),
+    })
+}
+
+/// Indices of the samples to fit on and the samples to score on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+/// Splits `0..n_samples` into `n_splits` contiguous test sets whose sizes
+/// differ by at most one. With a seed the samples are shuffled first.
+pub fn k_fold(
+    n_samples: usize,
+    n_splits: usize,
+    seed: Option<u64>,
+) -> Result<Vec<Fold>, ModelSelectionError> {
+    check_splits(n_samples, n_splits)?;
+
+    let mut indices: Vec<usize> = (0..n_samples).collect();
+    if let Some(seed) = seed {
+        indices.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+
+    // The first n % k folds take one extra sample
+    let mut assignment = vec![0; n_samples];
+    let mut start = 0;
+    for fold in 0..n_splits {
+        let size = n_samples / n_splits + usize::from(fold < n_samples % n_splits);
+        for &i in &indices[start..start + size] {
+            assignment[i] = fold;
+        }
+        start += size;
+    }
+
+    Ok(folds_from_assignment(&assignment, n_splits))
+}
+
+/// Like `k_fold`, but every test set keeps roughly the class proportions of
+/// `labels`: each class is dealt out over the folds in turn. With a seed
+/// the samples within each class are shuffled first.
+pub fn stratified_k_fold(
+    labels: &[usize],
+    n_splits: usize,
+    seed: Option<u64>,
+) -> Result<Vec<Fold>, ModelSelectionError> {
+    check_splits(labels.len(), n_splits)?;
+
+    let mut classes: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, &label) in labels.iter().enumerate() {
+        classes.entry(label).or_default().push(i);
+    }
+
+    let mut rng = seed.map(StdRng::seed_from_u64);
+    let mut assignment = vec![0; labels.len()];
+    // Carrying the position over between classes keeps the fold sizes even
+    let mut next = 0;
+    for members in classes.values_mut() {
+        if let Some(rng) = rng.as_mut() {
+            members.shuffle(rng);
+        }
+        for &i in members.iter() {
+            assignment[i] = next % n_splits;
+            next += 1;
+        }
+    }
+
+    Ok(folds_from_assignment(&assignment, n_splits))
+}
+
+/// One fold per sample, testing on that sample alone.
+pub fn leave_one_out(n_samples: usize) -> Result<Vec<Fold>, ModelSelectionError> {
+    check_splits(n_samples, n_samples.max(2))?;
+
+    Ok((0..n_samples)
+        .map(|i| Fold {
+            train: (0..n_samples).filter(|&j| j != i).collect(),
+            test: vec![i],
+        })
+        .collect())
+}
+
+/// Per-fold scores of a cross-validated model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvScores {
+    pub scores: Vec<f64>,
+}
+
+impl CvScores {
+    pub fn mean(&self) -> f64 {
+        self.scores.iter().sum::<f64>() / self.scores.len() as f64
+    }
+
+    /// Population standard deviation of the fold scores.
+    pub fn std(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .scores
+            .iter()
+            .map(|s| (s - mean) * (s - mean))
+            .sum::<f64>()
+            / self.scores.len() as f64;
+        variance.sqrt()
+    }
+}
+
+/// Fits a fresh model with `params` on the training samples of every fold
+/// and scores its predictions on the fold's test samples.
+///
+/// `score` compares true and predicted values, e.g. `metrics::r2`. Scores
+/// are reported as returned: for errors such as `metrics::mse`, lower is
+/// better. The model type usually has to be named, e.g.
+/// `cross_validate::<MultiLinRegModel, _>(&x, &y, &folds, &Solver::closed_form(), metrics::r2)`.
+pub fn cross_validate<M, S>(
+    x: &[Vec<f64>],
+    y: &[M::Target],
+    folds: &[Fold],
+    params: &M::Params,
+    score: S,
+) -> Result<CvScores, ModelSelectionError>
+where
+    M: Fit,
+    S: Fn(&[M::Target], &[M::Output]) -> Result<f64, MetricsError>,
+{
+    let mut scores = cross_validate_multi::<M, S>(x, y, folds, params, &[score])?;
+    Ok(scores.remove(0))
+}
+
+/// Like `cross_validate`, but scores the predictions of every fold with
+/// each of `scorers`, fitting the fold only once. Returns the scores of
+/// every scorer in the order given.
+pub fn cross_validate_multi<M, S>(
+    x: &[Vec<f64>],
+    y: &[M::Target],
+    folds: &[Fold],
+    params: &M::Params,
+    scorers: &[S],
+) -> Result<Vec<CvScores>, ModelSelectionError>
+where
+    M: Fit,
+    S: Fn(&[M::Target], &[M::Output]) -> Result<f64, MetricsError>,
+{
+    check_lengths(x.len(), y.len())?;
+
+    let mut scores = vec![Vec::with_capacity(folds.len()); scorers.len()];
+    for fold in folds {
+        let model = M::fit(&select(x, &fold.train), &select(y, &fold.train), params)?;
+        let predictions = model.predict(&select(x, &fold.test))?;
+        let y_test = select(y, &fold.test);
+        for (scores, score) in scores.iter_mut().zip(scorers) {
+            scores.push(score(&y_test, &predictions)?);
+        }
+    }
+
+    Ok(scores
+        .into_iter()
+        .map(|scores| CvScores { scores })
+        .collect())
+}
+
+fn check_lengths(rows: usize, targets: usize) -> Result<(), ModelSelectionError> {
+    if rows != targets {
+        return Err(ModelSelectionError::LengthMismatch { rows, targets });
+    }
+    Ok(())
+}
+
+fn check_splits(n_samples: usize, n_splits: usize) -> Result<(), ModelSelectionError> {
+    if n_splits < 2 {
+        return Err(ModelSelectionError::TooFewSplits(n_splits));
+    }
+    if n_samples < n_splits {
+        return Err(ModelSelectionError::TooFewSamples {
+            n_samples,
+            n_splits,
+        });
+    }
+    Ok(())
+}
+
+// Turns "sample i is tested in fold assignment[i]" into train/test indices.
+fn folds_from_assignment(assignment: &[usize], n_splits: usize) -> Vec<Fold> {
+    (0..n_splits)
+        .map(|fold| {
+            let (test, train) = (0..assignment.len()).partition(|&i| assignment[i] == fold);
+            Fold { train, test }
+        })
+        .collect()
+}
+
+fn select<T: Clone>(values: &[T], indices: &[usize]) -> Vec<T> {
+    indices.iter().map(|&i| values[i].clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics;
+    use crate::models::{MultiLinRegModel, Solver};
+
+    // Every sample is tested exactly once, and trained on in every other fold.
+    fn assert_partition(folds: &[Fold], n_samples: usize) {
+        let mut tested = vec![0; n_samples];
+        for fold in folds {
+            for &i in &fold.test {
+                tested[i] += 1;
+            }
+            let mut all: Vec<usize> = fold.train.iter().chain(&fold.test).copied().collect();
+            all.sort_unstable();
+            assert_eq!(all, (0..n_samples).collect::<Vec<_>>());
+        }
+        assert!(tested.iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn k_fold_sizes_differ_by_at_most_one() {
+        for seed in [None, Some(7)] {
+            let folds = k_fold(10, 3, seed).unwrap();
+            let sizes: Vec<usize> = folds.iter().map(|fold| fold.test.len()).collect();
+            assert_eq!(sizes, [4, 3, 3]);
+            assert_partition(&folds, 10);
+        }
+    }
+
+    #[test]
+    fn k_fold_without_seed_is_contiguous() {
+        let folds = k_fold(6, 3, None).unwrap();
+        assert_eq!(folds[1].test, [2, 3]);
+        assert_eq!(folds[1].train, [0, 1, 4, 5]);
+    }
+
+    #[test]
+    fn k_fold_rejects_bad_splits() {
+        assert_eq!(
+            k_fold(5, 1, None),
+            Err(ModelSelectionError::TooFewSplits(1))
+        );
+        assert_eq!(
+            k_fold(2, 3, None),
+            Err(ModelSelectionError::TooFewSamples {
+                n_samples: 2,
+                n_splits: 3
+            })
+        );
+    }
+
+    #[test]
+    fn stratified_k_fold_keeps_class_proportions() {
+        // 12 samples of class 0 and 6 of class 1, interleaved
+        let labels: Vec<usize> = (0..18).map(|i| usize::from(i % 3 == 2)).collect();
+        let folds = stratified_k_fold(&labels, 3, Some(1)).unwrap();
+        assert_partition(&folds, labels.len());
+        for fold in &folds {
+            let ones = fold.test.iter().filter(|&&i| labels[i] == 1).count();
+            assert_eq!((fold.test.len(), ones), (6, 2));
+        }
+    }
+
+    #[test]
+    fn leave_one_out_tests_every_sample_alone() {
+        let folds = leave_one_out(4).unwrap();
+        assert_eq!(folds.len(), 4);
+        assert!(folds.iter().all(|fold| fold.test.len() == 1));
+        assert_partition(&folds, 4);
+    }
+
+    #[test]
+    fn train_test_split_rounds_the_test_set_up() {
+        let x: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = (0..10).map(f64::from).collect();
+        let split = train_test_split(&x, &y, 0.25, 3).unwrap();
+        assert_eq!((split.x_train.len(), split.x_test.len()), (7, 3));
+        // Rows and targets are shuffled together
+        for (row, &target) in split.x_test.iter().zip(&split.y_test) {
+            assert_eq!(row[0], target);
+        }
+        assert_eq!(
+            train_test_split(&x, &y, 1.0, 3),
+            Err(ModelSelectionError::InvalidFraction(1.0))
+        );
+    }
+
+    #[test]
+    fn seeded_splits_are_repeatable() {
+        assert_eq!(k_fold(20, 4, Some(11)), k_fold(20, 4, Some(11)));
+        assert_ne!(k_fold(20, 4, Some(11)), k_fold(20, 4, Some(12)));
+
+        let labels: Vec<usize> = (0..20).map(|i| i % 3).collect();
+        assert_eq!(
+            stratified_k_fold(&labels, 4, Some(11)),
+            stratified_k_fold(&labels, 4, Some(11))
+        );
+
+        let x: Vec<Vec<f64>> = (0..20).map(|i| vec![i as f64]).collect();
+        let y: Vec<usize> = (0..20).collect();
+        assert_eq!(
+            train_test_split(&x, &y, 0.3, 5),
+            train_test_split(&x, &y, 0.3, 5)
+        );
+    }
+
+    #[test]
+    fn cross_validate_scores_every_fold() {
+        let x: Vec<Vec<f64>> = (0..12).map(|i| vec![i as f64, (i * i) as f64]).collect();
+        let y: Vec<f64> = x.iter().map(|row| 2.0 * row[0] - row[1] + 1.0).collect();
+        let folds = k_fold(x.len(), 4, Some(0)).unwrap();
+        let cv = cross_validate::<MultiLinRegModel, _>(
+            &x,
+            &y,
+            &folds,
+            &Solver::closed_form(),
+            metrics::mse,
+        )
+        .unwrap();
+        assert_eq!(cv.scores.len(), 4);
+        assert!(cv.mean() < 1e-12);
+    }
+
+    #[test]
+    fn cross_validate_multi_scores_the_same_fits() {
+        let x: Vec<Vec<f64>> = (0..12).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = (0..12).map(|i| f64::from(i % 5)).collect();
+        let folds = k_fold(x.len(), 3, Some(4)).unwrap();
+        let solver = Solver::closed_form();
+        let scorers = [metrics::mse, metrics::r2];
+        let cv =
+            cross_validate_multi::<MultiLinRegModel, _>(&x, &y, &folds, &solver, &scorers).unwrap();
+        for (scores, scorer) in cv.iter().zip(scorers) {
+            let single =
+                cross_validate::<MultiLinRegModel, _>(&x, &y, &folds, &solver, scorer).unwrap();
+            assert_eq!(scores, &single);
+        }
+    }
+}
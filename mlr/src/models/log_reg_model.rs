@@ -0,0 +1,535 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinalgError, ModelError};
+use crate::linalg::matrix::Matrix;
+use crate::models::{Fit, Predict, check_training_data};
+use crate::optim::{Optimizer, Sgd};
+use crate::random::rng_from_seed;
+use crate::training::{self, GradientDescent, TrainConfig, TrainingReport};
+
+/// Binary classifier: `P(y = 1 | x) = σ(x · w + b)` with the logistic
+/// sigmoid `σ(z) = 1 / (1 + e^(-z))`.
+///
+/// Labels are class indices `0` and `1`; features use the same `n × d`
+/// layout as `MultiLinRegModel`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogisticRegression {
+    pub w: Vec<f64>,
+    pub b: f64,
+}
+
+impl LogisticRegression {
+    /// Weights and bias drawn uniformly from `[-1, 1)`.
+    pub fn new(n_features: usize) -> Self {
+        Self::with_rng(n_features, &mut rand::thread_rng())
+    }
+
+    /// Like `new`, drawing from `rng` so the initialization can be repeated.
+    pub fn with_rng<R: Rng + ?Sized>(n_features: usize, rng: &mut R) -> Self {
+        Self {
+            w: (0..n_features).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+            b: rng.gen_range(-1.0..1.0),
+        }
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.w.len()
+    }
+
+    /// Probability of class `1` for every row of `x`.
+    pub fn predict_proba(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, LinalgError> {
+        Ok(self
+            .decision_function(x)?
+            .into_iter()
+            .map(sigmoid)
+            .collect())
+    }
+
+    /// Class `1` where its probability is at least 0.5, class `0` elsewhere.
+    pub fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<usize>, LinalgError> {
+        // σ(z) >= 0.5 exactly when z >= 0
+        Ok(self
+            .decision_function(x)?
+            .into_iter()
+            .map(|z| usize::from(z >= 0.0))
+            .collect())
+    }
+
+    /// The logit `x · w + b` for every row of `x`.
+    pub fn decision_function(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, LinalgError> {
+        if x.is_empty() {
+            return Ok(Vec::new());
+        }
+        let xw = Matrix::from_rows(x)?.mul_vector(&self.w)?;
+
+        Ok((xw + self.b).into_vec())
+    }
+
+    pub fn train(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[usize],
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<TrainingReport, ModelError> {
+        self.train_with(
+            x,
+            y,
+            &mut Sgd::new(learning_rate),
+            &TrainConfig::new(epochs),
+        )
+    }
+
+    /// Gradient descent on the binary cross-entropy, plus `config.penalty`
+    /// on `w`, with any optimizer and batching strategy.
+    ///
+    /// Parameters in the report's history are `[w_0, ..., w_{d-1}, b]`.
+    pub fn train_with(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[usize],
+        optimizer: &mut dyn Optimizer,
+        config: &TrainConfig,
+    ) -> Result<TrainingReport, ModelError> {
+        config.penalty.validate()?;
+        let x_mat = check_training_data(x, y.len())?;
+        check_labels(y, 2)?;
+        let n = x_mat.rows();
+        let d = self.n_features();
+        if x_mat.cols() != d {
+            return Err(LinalgError::DimensionMismatch {
+                expected: d,
+                found: x_mat.cols(),
+            }
+            .into());
+        }
+
+        // Parameters are packed as [w_0, ..., w_{d-1}, b]
+        let mut params: Vec<f64> = self.w.iter().copied().chain([self.b]).collect();
+
+        let report =
+            training::gradient_descent(&mut params, 0..d, n, optimizer, config, |params, batch| {
+                let (w, b) = params.split_at(d);
+                let mut grads = vec![0.0; d + 1];
+                let mut loss = 0.0;
+
+                // dL/dz = σ(z) - y for the cross-entropy of a sigmoid output
+                for &i in batch {
+                    let row = x_mat.row(i);
+                    let z = row.iter().zip(w).map(|(x_ij, w_j)| x_ij * w_j).sum::<f64>() + b[0];
+                    let target = y[i] as f64;
+                    let error = sigmoid(z) - target;
+                    for (g, x_ij) in grads.iter_mut().zip(row) {
+                        *g += error * x_ij;
+                    }
+                    grads[d] += error;
+                    // -[y·ln σ(z) + (1 - y)·ln(1 - σ(z))], without overflow
+                    loss += softplus(z) - target * z;
+                }
+
+                let m = batch.len() as f64;
+                grads.iter_mut().for_each(|g| *g /= m);
+                (loss / m, grads)
+            });
+
+        self.b = params.pop().unwrap_or(self.b);
+        self.w = params;
+
+        Ok(report)
+    }
+}
+
+/// Multinomial classifier over `k` classes: class scores `z_c = x · w_c + b_c`
+/// turned into probabilities with the softmax `e^(z_c) / Σ e^(z_j)`.
+///
+/// `w` holds one row of `d` weights per class.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoftmaxRegression {
+    pub w: Vec<Vec<f64>>,
+    pub b: Vec<f64>,
+}
+
+/// How a `SoftmaxRegression` is fitted: its number of classes and the
+/// gradient descent settings.
+///
+/// The classes are fixed up front rather than read off the labels, so a
+/// model fitted on a subset that misses the largest class, such as a
+/// cross-validation fold, still predicts every class.
+#[derive(Debug, Clone)]
+pub struct SoftmaxParams {
+    pub n_classes: usize,
+    pub gd: GradientDescent,
+}
+
+impl SoftmaxParams {
+    pub fn new(n_classes: usize, gd: GradientDescent) -> Self {
+        Self { n_classes, gd }
+    }
+}
+
+impl SoftmaxRegression {
+    /// Weights and biases drawn uniformly from `[-1, 1)`.
+    pub fn new(n_features: usize, n_classes: usize) -> Self {
+        Self::with_rng(n_features, n_classes, &mut rand::thread_rng())
+    }
+
+    /// Like `new`, drawing from `rng` so the initialization can be repeated.
+    pub fn with_rng<R: Rng + ?Sized>(n_features: usize, n_classes: usize, rng: &mut R) -> Self {
+        Self {
+            w: (0..n_classes)
+                .map(|_| (0..n_features).map(|_| rng.gen_range(-1.0..1.0)).collect())
+                .collect(),
+            b: (0..n_classes).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+        }
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.w.first().map_or(0, Vec::len)
+    }
+
+    pub fn n_classes(&self) -> usize {
+        self.b.len()
+    }
+
+    /// Class probabilities for every row of `x`, one entry per class.
+    pub fn predict_proba(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+        Ok(self
+            .decision_function(x)?
+            .iter()
+            .map(|scores| softmax(scores))
+            .collect())
+    }
+
+    /// The most probable class for every row of `x`.
+    pub fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<usize>, LinalgError> {
+        Ok(self
+            .decision_function(x)?
+            .iter()
+            .map(|scores| argmax(scores))
+            .collect())
+    }
+
+    /// The class scores `x · w_c + b_c` for every row of `x`.
+    pub fn decision_function(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+        if x.is_empty() {
+            return Ok(Vec::new());
+        }
+        let w_t = Matrix::from_rows(&self.w)?.transpose();
+        let scores = Matrix::from_rows(x)?.dot(&w_t)?;
+
+        Ok(scores
+            .iter_rows()
+            .map(|row| row.iter().zip(&self.b).map(|(z, b)| z + b).collect())
+            .collect())
+    }
+
+    pub fn train(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[usize],
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<TrainingReport, ModelError> {
+        self.train_with(
+            x,
+            y,
+            &mut Sgd::new(learning_rate),
+            &TrainConfig::new(epochs),
+        )
+    }
+
+    /// Gradient descent on the categorical cross-entropy, plus
+    /// `config.penalty` on `w`, with any optimizer and batching strategy.
+    ///
+    /// Parameters in the report's history are the rows of `w` one after
+    /// another, followed by `b`.
+    pub fn train_with(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[usize],
+        optimizer: &mut dyn Optimizer,
+        config: &TrainConfig,
+    ) -> Result<TrainingReport, ModelError> {
+        config.penalty.validate()?;
+        let x_mat = check_training_data(x, y.len())?;
+        let k = self.n_classes();
+        check_labels(y, k)?;
+        let n = x_mat.rows();
+        let d = self.n_features();
+        if x_mat.cols() != d {
+            return Err(LinalgError::DimensionMismatch {
+                expected: d,
+                found: x_mat.cols(),
+            }
+            .into());
+        }
+
+        // Parameters are packed as [w_0 (d values), ..., w_{k-1}, b_0, ..., b_{k-1}]
+        let mut params: Vec<f64> = self.w.iter().flatten().chain(&self.b).copied().collect();
+
+        let report = training::gradient_descent(
+            &mut params,
+            0..k * d,
+            n,
+            optimizer,
+            config,
+            |params, batch| {
+                let (w, b) = params.split_at(k * d);
+                let mut grads = vec![0.0; k * d + k];
+                let mut loss = 0.0;
+
+                // dL/dz_c = p_c - [c == y] for the cross-entropy of a softmax
+                for &i in batch {
+                    let row = x_mat.row(i);
+                    let scores: Vec<f64> = (0..k)
+                        .map(|c| {
+                            let w_c = &w[c * d..(c + 1) * d];
+                            row.iter()
+                                .zip(w_c)
+                                .map(|(x_ij, w_cj)| x_ij * w_cj)
+                                .sum::<f64>()
+                                + b[c]
+                        })
+                        .collect();
+                    let probabilities = softmax(&scores);
+                    loss -= probabilities[y[i]].max(f64::MIN_POSITIVE).ln();
+
+                    for (c, p_c) in probabilities.iter().enumerate() {
+                        let error = p_c - if c == y[i] { 1.0 } else { 0.0 };
+                        for (g, x_ij) in grads[c * d..(c + 1) * d].iter_mut().zip(row) {
+                            *g += error * x_ij;
+                        }
+                        grads[k * d + c] += error;
+                    }
+                }
+
+                let m = batch.len() as f64;
+                grads.iter_mut().for_each(|g| *g /= m);
+                (loss / m, grads)
+            },
+        );
+
+        self.b = params.split_off(k * d);
+        self.w = (0..k)
+            .map(|c| params[c * d..(c + 1) * d].to_vec())
+            .collect();
+
+        Ok(report)
+    }
+}
+
+impl Predict for LogisticRegression {
+    type Output = usize;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<usize>, ModelError> {
+        Ok(LogisticRegression::predict(self, x)?)
+    }
+}
+
+impl Fit for LogisticRegression {
+    type Target = usize;
+    type Params = GradientDescent;
+
+    fn fit_with_report(
+        x: &[Vec<f64>],
+        y: &[usize],
+        gd: &GradientDescent,
+    ) -> Result<(Self, Option<TrainingReport>), ModelError> {
+        let n_features = x.first().map_or(0, Vec::len);
+        let mut model = Self::with_rng(n_features, &mut rng_from_seed(gd.config.seed));
+        let mut optimizer = gd.optimizer.build(gd.learning_rate);
+        let report = model.train_with(x, y, optimizer.as_mut(), &gd.config)?;
+        Ok((model, Some(report)))
+    }
+}
+
+impl Predict for SoftmaxRegression {
+    type Output = usize;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<usize>, ModelError> {
+        Ok(SoftmaxRegression::predict(self, x)?)
+    }
+}
+
+/// Fails with `ModelError::LabelOutOfRange` if a label in `y` isn't below
+/// `params.n_classes`.
+impl Fit for SoftmaxRegression {
+    type Target = usize;
+    type Params = SoftmaxParams;
+
+    fn fit_with_report(
+        x: &[Vec<f64>],
+        y: &[usize],
+        params: &SoftmaxParams,
+    ) -> Result<(Self, Option<TrainingReport>), ModelError> {
+        let gd = &params.gd;
+        let n_features = x.first().map_or(0, Vec::len);
+        let mut rng = rng_from_seed(gd.config.seed);
+        let mut model = Self::with_rng(n_features, params.n_classes, &mut rng);
+        let mut optimizer = gd.optimizer.build(gd.learning_rate);
+        let report = model.train_with(x, y, optimizer.as_mut(), &gd.config)?;
+        Ok((model, Some(report)))
+    }
+}
+
+pub fn sigmoid(z: f64) -> f64 {
+    // Written so that neither branch computes e^(large)
+    if z >= 0.0 {
+        1.0 / (1.0 + (-z).exp())
+    } else {
+        let e = z.exp();
+        e / (1.0 + e)
+    }
+}
+
+/// Probabilities `e^(z_c) / Σ e^(z_j)`, shifted by the largest score so
+/// that no exponential overflows.
+pub fn softmax(scores: &[f64]) -> Vec<f64> {
+    let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = scores.iter().map(|z| (z - max).exp()).collect();
+    let sum: f64 = exps.iter().sum();
+    exps.into_iter().map(|e| e / sum).collect()
+}
+
+// ln(1 + e^z), stable for large |z|.
+fn softplus(z: f64) -> f64 {
+    z.max(0.0) + (-z.abs()).exp().ln_1p()
+}
+
+// Index of the largest score; the first one wins ties.
+fn argmax(scores: &[f64]) -> usize {
+    scores
+        .iter()
+        .enumerate()
+        .fold((0, f64::NEG_INFINITY), |best, (c, &z)| {
+            if z > best.1 { (c, z) } else { best }
+        })
+        .0
+}
+
+fn check_labels(y: &[usize], n_classes: usize) -> Result<(), ModelError> {
+    match y.iter().find(|&&label| label >= n_classes) {
+        Some(&label) => Err(ModelError::LabelOutOfRange { label, n_classes }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn separable() -> (Vec<Vec<f64>>, Vec<usize>) {
+        let x = vec![
+            vec![-2.0],
+            vec![-1.5],
+            vec![-1.0],
+            vec![1.0],
+            vec![1.5],
+            vec![2.0],
+        ];
+        (x, vec![0, 0, 0, 1, 1, 1])
+    }
+
+    #[test]
+    fn logistic_fit_separates_the_classes() {
+        let (x, y) = separable();
+        let gd = GradientDescent::new(0.5, 500).config(TrainConfig::new(500).seed(3));
+        let (model, report) = LogisticRegression::fit_with_report(&x, &y, &gd).unwrap();
+        assert_eq!(model.predict(&x).unwrap(), y);
+        assert!(model.w[0] > 0.0);
+
+        let report = report.unwrap();
+        assert_eq!(report.epochs_run, 500);
+        assert!(report.final_loss < 0.1);
+
+        let proba = model.predict_proba(&x).unwrap();
+        assert!(proba.windows(2).all(|p| p[0] < p[1]));
+        assert!(proba[..3].iter().all(|&p| p < 0.5));
+        assert!(proba[3..].iter().all(|&p| p > 0.5));
+    }
+
+    #[test]
+    fn logistic_predicts_class_one_from_probability_one_half() {
+        // z = 2·x - 2 is zero at x = 1
+        let model = LogisticRegression {
+            w: vec![2.0],
+            b: -2.0,
+        };
+        let x = vec![vec![0.9], vec![1.0], vec![1.1]];
+        assert_eq!(model.decision_function(&x).unwrap()[1], 0.0);
+        let proba = model.predict_proba(&x).unwrap();
+        assert_eq!(proba[1], 0.5);
+        assert!(proba[0] < 0.5 && proba[2] > 0.5);
+        assert_eq!(model.predict(&x).unwrap(), [0, 1, 1]);
+
+        assert_eq!(sigmoid(1000.0), 1.0);
+        assert_eq!(sigmoid(-1000.0), 0.0);
+        assert!(model.predict(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn logistic_rejects_labels_other_than_zero_and_one() {
+        let (x, _) = separable();
+        let gd = GradientDescent::new(0.5, 10);
+        assert_eq!(
+            LogisticRegression::fit(&x, &[0, 1, 2, 1, 0, 1], &gd),
+            Err(ModelError::LabelOutOfRange {
+                label: 2,
+                n_classes: 2
+            })
+        );
+    }
+
+    #[test]
+    fn logistic_checks_the_feature_count() {
+        let (x, y) = separable();
+        let mut model = LogisticRegression::new(2);
+        assert_eq!(
+            model.predict(&x),
+            Err(LinalgError::DimensionMismatch {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            model.train(&x, &y, 0.1, 10).unwrap_err(),
+            LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 1
+            }
+            .into()
+        );
+        assert_eq!(
+            model.train(&x, &y[..5], 0.1, 10).unwrap_err(),
+            LinalgError::DimensionMismatch {
+                expected: 6,
+                found: 5
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn softmax_fit_keeps_classes_missing_from_the_labels() {
+        let x = vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]];
+        let y = [0, 0, 1, 1];
+        let params = SoftmaxParams::new(3, GradientDescent::new(0.1, 10));
+        let model = SoftmaxRegression::fit(&x, &y, &params).unwrap();
+        assert_eq!(model.n_classes(), 3);
+        assert_eq!(model.predict_proba(&x).unwrap()[0].len(), 3);
+    }
+
+    #[test]
+    fn softmax_fit_rejects_labels_past_n_classes() {
+        let x = vec![vec![0.0], vec![1.0]];
+        let params = SoftmaxParams::new(2, GradientDescent::new(0.1, 10));
+        assert_eq!(
+            SoftmaxRegression::fit(&x, &[0, 2], &params),
+            Err(ModelError::LabelOutOfRange {
+                label: 2,
+                n_classes: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Regression and classification models.
+//!
+//! Every model implements [`Predict`] and [`Fit`], so generic code can fit,
+//! score and save any of them the same way.
+
+pub mod log_reg_model;
+pub mod multi_li_reg_model;
+pub mod pipeline;
+pub mod poly_reg_model;
+pub mod simple_li_reg_model;
+
+pub use log_reg_model::{LogisticRegression, SoftmaxParams, SoftmaxRegression};
+pub use multi_li_reg_model::MultiLinRegModel;
+pub use pipeline::{Pipeline, PipelineParams};
+pub use poly_reg_model::{PolyRegModel, PolyRegParams};
+pub use simple_li_reg_model::LinRegModel;
+
+use crate::error::{LinalgError, ModelError};
+use crate::linalg::matrix::Matrix;
+use crate::regularization::Penalty;
+use crate::training::{GradientDescent, TrainingReport};
+
+/// A fitted model that can be applied to new `n × d` feature rows.
+///
+/// Every model implements it, so code such as
+/// `model_selection::cross_validate` can score any of them the same way.
+pub trait Predict {
+    /// `f64` for regressors, a class index for classifiers.
+    type Output;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<Self::Output>, ModelError>;
+}
+
+/// A model that can be fitted from scratch to `n × d` feature rows.
+///
+/// `Params` holds every setting that decides the fit, so generic code such
+/// as `model_selection::cross_validate` can refit a model on any subset of
+/// the data, or compare several settings.
+pub trait Fit: Predict + Sized {
+    /// `f64` for regressors, a class index for classifiers.
+    type Target: Clone;
+    type Params: Clone;
+
+    /// Fits a new model, along with the training report when the fit is
+    /// iterative.
+    fn fit_with_report(
+        x: &[Vec<f64>],
+        y: &[Self::Target],
+        params: &Self::Params,
+    ) -> Result<(Self, Option<TrainingReport>), ModelError>;
+
+    fn fit(x: &[Vec<f64>], y: &[Self::Target], params: &Self::Params) -> Result<Self, ModelError> {
+        Self::fit_with_report(x, y, params).map(|(model, _)| model)
+    }
+}
+
+/// How a linear regression model is fitted.
+#[derive(Debug, Clone)]
+pub enum Solver {
+    /// The exact minimizer of `MSE + penalty`: the ridge normal equation,
+    /// or coordinate descent when the penalty has an L1 part, stopping after
+    /// `max_iter` sweeps or once no parameter moves by more than `tolerance`.
+    Exact {
+        penalty: Penalty,
+        max_iter: usize,
+        tolerance: f64,
+    },
+    /// Gradient descent on the MSE plus `config.penalty`.
+    GradientDescent(GradientDescent),
+}
+
+impl Solver {
+    /// Ordinary least squares through the normal equation.
+    pub fn closed_form() -> Self {
+        Solver::exact(Penalty::None)
+    }
+
+    /// The exact fit under `penalty`, with up to 1000 coordinate-descent
+    /// sweeps and a tolerance of `1e-8` if it needs them.
+    pub fn exact(penalty: Penalty) -> Self {
+        Solver::Exact {
+            penalty,
+            max_iter: 1000,
+            tolerance: 1e-8,
+        }
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::closed_form()
+    }
+}
+
+// Checks that `x` has one row per target and at least one row.
+pub(crate) fn check_training_data(x: &[Vec<f64>], n_targets: usize) -> Result<Matrix, LinalgError> {
+    if x.len() != n_targets {
+        return Err(LinalgError::DimensionMismatch {
+            expected: x.len(),
+            found: n_targets,
+        });
+    }
+    if x.is_empty() {
+        return Err(LinalgError::Empty);
+    }
+
+    Matrix::from_rows(x)
+}
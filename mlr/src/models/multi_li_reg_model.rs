@@ -0,0 +1,258 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinalgError, ModelError};
+use crate::linalg::matrix::Matrix;
+use crate::models::{Fit, Predict, Solver, check_training_data};
+use crate::optim::{Optimizer, Sgd};
+use crate::random::rng_from_seed;
+use crate::regularization::{Penalty, soft_threshold};
+use crate::training::{self, TrainConfig, TrainingReport};
+
+/// Linear regression over `d` features: `y = x · w + b`.
+///
+/// Inputs are given as an `n × d` feature matrix (one row per sample), the
+/// same `&[Vec<f64>]` layout used by `matrix_functions`; internally the data
+/// is copied into a `Matrix` once per call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultiLinRegModel {
+    pub w: Vec<f64>,
+    pub b: f64,
+}
+
+impl MultiLinRegModel {
+    /// Weights and bias drawn uniformly from `[-1, 1)`.
+    pub fn new(n_features: usize) -> Self {
+        Self::with_rng(n_features, &mut rand::thread_rng())
+    }
+
+    /// Like `new`, drawing from `rng` so the initialization can be repeated.
+    pub fn with_rng<R: Rng + ?Sized>(n_features: usize, rng: &mut R) -> Self {
+        Self {
+            w: (0..n_features).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+            b: rng.gen_range(-1.0..1.0),
+        }
+    }
+
+    /// Solves the normal equation `(XᵀX)θ = Xᵀy` exactly, where `X` is `x`
+    /// with a trailing column of ones so the last entry of `θ` is the bias.
+    /// The system is solved through an LU decomposition rather than by
+    /// forming `(XᵀX)⁻¹`.
+    pub fn fit_closed_form(x: &[Vec<f64>], y: &[f64]) -> Result<Self, LinalgError> {
+        Self::fit_ridge(x, y, 0.0)
+    }
+
+    /// Solves the ridge normal equation `(XᵀX + nα·I')θ = Xᵀy`, the exact
+    /// minimizer of `MSE + α·‖w‖²`. `I'` is the identity without the bias
+    /// entry, so the bias isn't shrunk. Any `alpha > 0` also makes the system
+    /// solvable when features are collinear.
+    pub fn fit_ridge(x: &[Vec<f64>], y: &[f64], alpha: f64) -> Result<Self, LinalgError> {
+        let x_mat = check_training_data(x, y.len())?;
+        let (n, d) = x_mat.shape();
+        let design = Matrix::from_fn(n, d + 1, |i, j| if j < d { x_mat[(i, j)] } else { 1.0 });
+
+        let design_t = design.transpose();
+        let mut gram = design_t.dot(&design)?;
+        for j in 0..d {
+            gram[(j, j)] += n as f64 * alpha;
+        }
+        let moment = design_t.dot(&Matrix::column_vector(y))?;
+
+        let mut w = gram.solve(moment.as_slice())?;
+        let b = w.pop().unwrap_or(0.0);
+
+        Ok(Self { w, b })
+    }
+
+    /// Minimizes `MSE + penalty` by cyclic coordinate descent, the usual
+    /// solver for lasso and elastic net: each sweep sets every weight to its
+    /// exact minimizer with the others held fixed, then refits the bias.
+    /// Stops after `max_iter` sweeps or once no parameter moves by more
+    /// than `tolerance` in a sweep.
+    pub fn fit_coordinate_descent(
+        x: &[Vec<f64>],
+        y: &[f64],
+        penalty: Penalty,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> Result<Self, ModelError> {
+        penalty.validate()?;
+        let x_mat = check_training_data(x, y.len())?;
+        let (n, d) = x_mat.shape();
+        let scale = 2.0 / n as f64;
+        let (l1, l2) = (penalty.l1_strength(), penalty.l2_strength());
+
+        let mut w = vec![0.0; d];
+        let mut b = y.iter().sum::<f64>() / n as f64;
+        // residual_i = y_i - x_i · w - b, kept up to date as parameters move
+        let mut residual: Vec<f64> = y.iter().map(|y_i| y_i - b).collect();
+        let column_norms: Vec<f64> = (0..d)
+            .map(|j| scale * x_mat.column(j).iter().map(|x_ij| x_ij * x_ij).sum::<f64>())
+            .collect();
+
+        for _ in 0..max_iter {
+            let mut max_change: f64 = 0.0;
+
+            for j in 0..d {
+                if column_norms[j] == 0.0 {
+                    continue; // An all-zero feature keeps a zero weight
+                }
+                // Correlation of feature j with the residual that excludes it
+                let rho = scale * (0..n).map(|i| x_mat[(i, j)] * residual[i]).sum::<f64>()
+                    + column_norms[j] * w[j];
+                let w_j = soft_threshold(rho, l1) / (column_norms[j] + 2.0 * l2);
+
+                let change = w_j - w[j];
+                if change != 0.0 {
+                    for (i, r) in residual.iter_mut().enumerate() {
+                        *r -= x_mat[(i, j)] * change;
+                    }
+                    w[j] = w_j;
+                }
+                max_change = max_change.max(change.abs());
+            }
+
+            // The unpenalized bias is optimal at the mean residual
+            let shift = residual.iter().sum::<f64>() / n as f64;
+            residual.iter_mut().for_each(|r| *r -= shift);
+            b += shift;
+            max_change = max_change.max(shift.abs());
+
+            if max_change < tolerance {
+                break;
+            }
+        }
+
+        Ok(Self { w, b })
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.w.len()
+    }
+
+    pub fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, LinalgError> {
+        if x.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.predict_matrix(&Matrix::from_rows(x)?)
+    }
+
+    pub fn predict_matrix(&self, x: &Matrix) -> Result<Vec<f64>, LinalgError> {
+        let xw = x.mul_vector(&self.w)?;
+
+        Ok((xw + self.b).into_vec())
+    }
+
+    pub fn train(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[f64],
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<TrainingReport, ModelError> {
+        self.train_with(
+            x,
+            y,
+            &mut Sgd::new(learning_rate),
+            &TrainConfig::new(epochs),
+        )
+    }
+
+    /// Gradient descent on the MSE, plus `config.penalty` on `w`, with any
+    /// optimizer and batching strategy.
+    ///
+    /// Parameters in the report's history are `[w_0, ..., w_{d-1}, b]`.
+    pub fn train_with(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[f64],
+        optimizer: &mut dyn Optimizer,
+        config: &TrainConfig,
+    ) -> Result<TrainingReport, ModelError> {
+        config.penalty.validate()?;
+        let x_mat = check_training_data(x, y.len())?;
+        let n = x_mat.rows();
+        let d = self.n_features();
+        if x_mat.cols() != d {
+            return Err(LinalgError::DimensionMismatch {
+                expected: d,
+                found: x_mat.cols(),
+            }
+            .into());
+        }
+
+        // Parameters are packed as [w_0, ..., w_{d-1}, b]
+        let mut params: Vec<f64> = self.w.iter().copied().chain([self.b]).collect();
+
+        let report =
+            training::gradient_descent(&mut params, 0..d, n, optimizer, config, |params, batch| {
+                let (w, b) = params.split_at(d);
+                let mut grads = vec![0.0; d + 1];
+                let mut loss = 0.0;
+
+                // dL/dw = 2/m · Σ (ŷ_i - y_i)·x_i, dL/db = 2/m · Σ (ŷ_i - y_i)
+                for &i in batch {
+                    let row = x_mat.row(i);
+                    let y_pred: f64 = row.iter().zip(w).map(|(x_ij, w_j)| x_ij * w_j).sum();
+                    let residual = y_pred + b[0] - y[i];
+                    for (g, x_ij) in grads.iter_mut().zip(row) {
+                        *g += residual * x_ij;
+                    }
+                    grads[d] += residual;
+                    loss += residual * residual;
+                }
+
+                let m = batch.len() as f64;
+                grads.iter_mut().for_each(|g| *g *= 2.0 / m);
+                (loss / m, grads)
+            });
+
+        self.b = params.pop().unwrap_or(self.b);
+        self.w = params;
+
+        Ok(report)
+    }
+}
+
+impl Predict for MultiLinRegModel {
+    type Output = f64;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, ModelError> {
+        Ok(MultiLinRegModel::predict(self, x)?)
+    }
+}
+
+impl Fit for MultiLinRegModel {
+    type Target = f64;
+    type Params = Solver;
+
+    fn fit_with_report(
+        x: &[Vec<f64>],
+        y: &[f64],
+        solver: &Solver,
+    ) -> Result<(Self, Option<TrainingReport>), ModelError> {
+        match solver {
+            Solver::Exact {
+                penalty,
+                max_iter,
+                tolerance,
+            } => {
+                penalty.validate()?;
+                let model = if penalty.has_l1() {
+                    Self::fit_coordinate_descent(x, y, *penalty, *max_iter, *tolerance)?
+                } else {
+                    Self::fit_ridge(x, y, penalty.l2_strength())?
+                };
+                Ok((model, None))
+            }
+            Solver::GradientDescent(gd) => {
+                let n_features = x.first().map_or(0, Vec::len);
+                let mut model = Self::with_rng(n_features, &mut rng_from_seed(gd.config.seed));
+                let mut optimizer = gd.optimizer.build(gd.learning_rate);
+                let report = model.train_with(x, y, optimizer.as_mut(), &gd.config)?;
+                Ok((model, Some(report)))
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ModelError, PreprocessingError};
+use crate::models::{Fit, Predict};
+use crate::preprocessing::{FeatureTransform, Transformer};
+use crate::training::TrainingReport;
+
+/// A model fitted on transformed features, together with the fitted
+/// transformations, so it takes and predicts on raw inputs.
+///
+/// `steps` are applied in order; each was fitted on the output of the one
+/// before it. Saving a pipeline saves the imputation, encoding and scaling
+/// with the model, so whoever loads it doesn't have to repeat them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pipeline<M> {
+    pub steps: Vec<FeatureTransform>,
+    pub model: M,
+}
+
+/// How a `Pipeline` is fitted: the unfitted steps, then the parameters of
+/// the model fitted on their output.
+#[derive(Debug, Clone)]
+pub struct PipelineParams<P> {
+    pub steps: Vec<FeatureTransform>,
+    pub model: P,
+}
+
+impl<P> PipelineParams<P> {
+    /// No steps yet; the model is fitted on the raw features.
+    pub fn new(model: P) -> Self {
+        Self {
+            steps: Vec::new(),
+            model,
+        }
+    }
+
+    /// Appends a step after the existing ones.
+    pub fn step(mut self, step: impl Into<FeatureTransform>) -> Self {
+        self.steps.push(step.into());
+        self
+    }
+}
+
+impl<M> Pipeline<M> {
+    /// Runs `x` through every step, giving the features the model sees.
+    pub fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        let mut x = x.to_vec();
+        for step in &self.steps {
+            x = step.transform(&x)?;
+        }
+        Ok(x)
+    }
+
+    /// Maps model-space features back to raw inputs by undoing the steps in
+    /// reverse order.
+    pub fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        let mut x = x.to_vec();
+        for step in self.steps.iter().rev() {
+            x = step.inverse_transform(&x)?;
+        }
+        Ok(x)
+    }
+
+    /// The same steps in front of another model, e.g. to turn a
+    /// `Pipeline<MultiLinRegModel>` into a `Pipeline<SavedModel>`.
+    pub fn map_model<N>(self, f: impl FnOnce(M) -> N) -> Pipeline<N> {
+        Pipeline {
+            steps: self.steps,
+            model: f(self.model),
+        }
+    }
+}
+
+impl<M: Predict> Predict for Pipeline<M> {
+    type Output = M::Output;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<M::Output>, ModelError> {
+        self.model.predict(&self.transform(x)?)
+    }
+}
+
+impl<M: Fit> Fit for Pipeline<M> {
+    type Target = M::Target;
+    type Params = PipelineParams<M::Params>;
+
+    fn fit_with_report(
+        x: &[Vec<f64>],
+        y: &[M::Target],
+        params: &PipelineParams<M::Params>,
+    ) -> Result<(Self, Option<TrainingReport>), ModelError> {
+        let mut steps = params.steps.clone();
+        let mut x = x.to_vec();
+        for step in steps.iter_mut() {
+            x = step.fit_transform(&x)?;
+        }
+        let (model, report) = M::fit_with_report(&x, y, &params.model)?;
+
+        Ok((Self { steps, model }, report))
+    }
+}
@@ -0,0 +1,231 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinalgError, ModelError};
+use crate::models::multi_li_reg_model::MultiLinRegModel;
+use crate::models::{Fit, Predict, Solver};
+use crate::optim::{Optimizer, Sgd};
+use crate::preprocessing::PolynomialFeatures;
+use crate::training::{TrainConfig, TrainingReport};
+
+/// Polynomial regression: a `MultiLinRegModel` fitted on the
+/// `PolynomialFeatures` expansion of the inputs.
+///
+/// Inputs are the raw `n × d` features; the expansion happens inside
+/// `train`, `fit_*` and `predict`. Gradient descent on high-degree terms
+/// needs a small learning rate or scaled inputs, so the closed-form fits are
+/// usually the better choice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolyRegModel {
+    pub features: PolynomialFeatures,
+    pub linear: MultiLinRegModel,
+}
+
+/// How a `PolyRegModel` is fitted: the expansion, then the solver for the
+/// linear model on the expanded features.
+#[derive(Debug, Clone)]
+pub struct PolyRegParams {
+    pub features: PolynomialFeatures,
+    pub solver: Solver,
+}
+
+impl PolyRegParams {
+    /// The expansion `features`, fitted in closed form.
+    pub fn new(features: PolynomialFeatures) -> Self {
+        Self {
+            features,
+            solver: Solver::default(),
+        }
+    }
+
+    pub fn solver(mut self, solver: Solver) -> Self {
+        self.solver = solver;
+        self
+    }
+}
+
+impl PolyRegModel {
+    pub fn new(n_features: usize, features: PolynomialFeatures) -> Self {
+        Self::with_rng(n_features, features, &mut rand::thread_rng())
+    }
+
+    /// Like `new`, drawing from `rng` so the initialization can be repeated.
+    pub fn with_rng<R: Rng + ?Sized>(
+        n_features: usize,
+        features: PolynomialFeatures,
+        rng: &mut R,
+    ) -> Self {
+        let n_expanded = features.n_output_features(n_features);
+        Self {
+            features,
+            linear: MultiLinRegModel::with_rng(n_expanded, rng),
+        }
+    }
+
+    /// Fits the expanded features exactly with the normal equation.
+    pub fn fit_closed_form(
+        x: &[Vec<f64>],
+        y: &[f64],
+        features: PolynomialFeatures,
+    ) -> Result<Self, LinalgError> {
+        Self::fit_ridge(x, y, features, 0.0)
+    }
+
+    /// Fits the expanded features exactly under a ridge penalty, which tames
+    /// the large coefficients high degrees tend to produce.
+    pub fn fit_ridge(
+        x: &[Vec<f64>],
+        y: &[f64],
+        features: PolynomialFeatures,
+        alpha: f64,
+    ) -> Result<Self, LinalgError> {
+        let expanded = features.transform(x)?;
+        let linear = MultiLinRegModel::fit_ridge(&expanded, y, alpha)?;
+
+        Ok(Self { features, linear })
+    }
+
+    pub fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, LinalgError> {
+        self.linear.predict(&self.features.transform(x)?)
+    }
+
+    pub fn train(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[f64],
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<TrainingReport, ModelError> {
+        self.train_with(
+            x,
+            y,
+            &mut Sgd::new(learning_rate),
+            &TrainConfig::new(epochs),
+        )
+    }
+
+    /// Gradient descent on the expanded features. See
+    /// `MultiLinRegModel::train_with`; the history's parameters follow
+    /// `PolynomialFeatures::terms`, then the bias.
+    pub fn train_with(
+        &mut self,
+        x: &[Vec<f64>],
+        y: &[f64],
+        optimizer: &mut dyn Optimizer,
+        config: &TrainConfig,
+    ) -> Result<TrainingReport, ModelError> {
+        let expanded = self.features.transform(x)?;
+        self.linear.train_with(&expanded, y, optimizer, config)
+    }
+}
+
+impl Predict for PolyRegModel {
+    type Output = f64;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, ModelError> {
+        Ok(PolyRegModel::predict(self, x)?)
+    }
+}
+
+impl Fit for PolyRegModel {
+    type Target = f64;
+    type Params = PolyRegParams;
+
+    fn fit_with_report(
+        x: &[Vec<f64>],
+        y: &[f64],
+        params: &PolyRegParams,
+    ) -> Result<(Self, Option<TrainingReport>), ModelError> {
+        let expanded = params.features.transform(x)?;
+        let (linear, report) = MultiLinRegModel::fit_with_report(&expanded, y, &params.solver)?;
+
+        Ok((
+            Self {
+                features: params.features.clone(),
+                linear,
+            },
+            report,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::rng_from_seed;
+    use crate::training::GradientDescent;
+
+    // y = 0.5·x² - 2·x + 1 on x = -2, -1.5, ..., 2
+    fn quadratic() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x: Vec<Vec<f64>> = (-4..=4).map(|i| vec![i as f64 / 2.0]).collect();
+        let y = x
+            .iter()
+            .map(|row| 0.5 * row[0] * row[0] - 2.0 * row[0] + 1.0)
+            .collect();
+        (x, y)
+    }
+
+    fn assert_close(found: &[f64], expected: &[f64], tolerance: f64) {
+        assert_eq!(found.len(), expected.len());
+        for (f, e) in found.iter().zip(expected) {
+            assert!((f - e).abs() < tolerance, "{:?} != {:?}", found, expected);
+        }
+    }
+
+    #[test]
+    fn closed_form_recovers_a_known_quadratic() {
+        let (x, y) = quadratic();
+        let model = PolyRegModel::fit_closed_form(&x, &y, PolynomialFeatures::new(2)).unwrap();
+        // Weights follow the terms [x, x²]
+        assert_close(&model.linear.w, &[-2.0, 0.5], 1e-10);
+        assert!((model.linear.b - 1.0).abs() < 1e-10);
+
+        let unseen = [vec![3.0], vec![-5.0]];
+        assert_close(&model.predict(&unseen).unwrap(), &[-0.5, 23.5], 1e-9);
+    }
+
+    #[test]
+    fn fit_uses_the_solver_in_the_params() {
+        let (x, y) = quadratic();
+        let params = PolyRegParams::new(PolynomialFeatures::new(2));
+        let (exact, report) = PolyRegModel::fit_with_report(&x, &y, &params).unwrap();
+        assert!(report.is_none());
+        assert_close(&exact.linear.w, &[-2.0, 0.5], 1e-10);
+
+        let gd = GradientDescent::new(0.05, 5000).config(TrainConfig::new(5000).seed(1));
+        let params = params.solver(Solver::GradientDescent(gd));
+        let (trained, report) = PolyRegModel::fit_with_report(&x, &y, &params).unwrap();
+        assert_eq!(report.unwrap().epochs_run, 5000);
+        assert_close(&trained.linear.w, &exact.linear.w, 1e-6);
+        assert!((trained.linear.b - exact.linear.b).abs() < 1e-6);
+    }
+
+    #[test]
+    fn interaction_terms_fit_a_product_of_features() {
+        // y = 1 + a·b needs the cross term but no squares
+        let x: Vec<Vec<f64>> = (0..9)
+            .map(|i| vec![(i % 3) as f64 - 1.0, (i / 3) as f64 * 0.5])
+            .collect();
+        let y: Vec<f64> = x.iter().map(|row| 1.0 + row[0] * row[1]).collect();
+
+        let features = PolynomialFeatures::new(2).interaction_only(true);
+        let model = PolyRegModel::fit_closed_form(&x, &y, features).unwrap();
+        assert_eq!(model.linear.n_features(), 3);
+        assert_close(&model.linear.w, &[0.0, 0.0, 1.0], 1e-10);
+        assert_close(&model.predict(&[vec![4.0, 2.0]]).unwrap(), &[9.0], 1e-9);
+    }
+
+    #[test]
+    fn initial_weights_match_the_expansion() {
+        let features = PolynomialFeatures::new(3);
+        let model = PolyRegModel::with_rng(2, features.clone(), &mut rng_from_seed(Some(5)));
+        assert_eq!(model.linear.n_features(), features.n_output_features(2));
+        assert_eq!(
+            model.predict(&[vec![1.0]]),
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 9
+            })
+        );
+    }
+}
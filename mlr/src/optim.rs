@@ -0,0 +1,370 @@
+//! Parameter update rules for gradient-based training.
+//!
+//! An [`Optimizer`] only decides how a gradient turns into a parameter
+//! update; which samples the gradient is computed on is chosen separately by
+//! `training::Batching`, so every rule works for full-batch, mini-batch and
+//! stochastic training.
+
+/// Turns gradients into parameter updates, keeping whatever per-parameter
+/// state the rule needs between steps.
+pub trait Optimizer {
+    /// Updates `params` in place from the gradient of the loss at `params`.
+    fn step(&mut self, params: &mut [f64], grads: &[f64]);
+
+    /// Short identifier, e.g. `"adam"`, recorded alongside trained models.
+    fn name(&self) -> &'static str;
+
+    fn learning_rate(&self) -> f64;
+
+    /// Whether every step moves each parameter by exactly
+    /// `learning_rate · g`, so an L1 penalty can follow it with a proximal
+    /// step of that size. Rules with momentum or per-parameter scaling
+    /// don't, and get the L1 subgradient instead.
+    fn plain_steps(&self) -> bool {
+        false
+    }
+
+    /// Forgets accumulated state so the optimizer can start a new run.
+    fn reset(&mut self) {}
+}
+
+/// An update rule and its settings other than the learning rate, for
+/// building a fresh optimizer for every training run (see
+/// `training::GradientDescent`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OptimizerKind {
+    #[default]
+    Sgd,
+    Momentum {
+        beta: f64,
+    },
+    Nesterov {
+        beta: f64,
+    },
+    RmsProp,
+    Adam,
+}
+
+impl OptimizerKind {
+    pub fn build(self, learning_rate: f64) -> Box<dyn Optimizer> {
+        match self {
+            OptimizerKind::Sgd => Box::new(Sgd::new(learning_rate)),
+            OptimizerKind::Momentum { beta } => Box::new(Momentum::new(learning_rate, beta)),
+            OptimizerKind::Nesterov { beta } => Box::new(Momentum::nesterov(learning_rate, beta)),
+            OptimizerKind::RmsProp => Box::new(RmsProp::new(learning_rate)),
+            OptimizerKind::Adam => Box::new(Adam::new(learning_rate)),
+        }
+    }
+}
+
+/// Plain gradient descent: `p -= lr · g`.
+#[derive(Debug, Clone)]
+pub struct Sgd {
+    pub learning_rate: f64,
+}
+
+impl Sgd {
+    pub fn new(learning_rate: f64) -> Self {
+        Self { learning_rate }
+    }
+}
+
+impl Optimizer for Sgd {
+    fn step(&mut self, params: &mut [f64], grads: &[f64]) {
+        for (p, g) in params.iter_mut().zip(grads) {
+            *p -= self.learning_rate * g;
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "sgd"
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn plain_steps(&self) -> bool {
+        true
+    }
+}
+
+/// Gradient descent with a velocity term, optionally Nesterov-accelerated.
+///
+/// `v = β·v + g`, then `p -= lr · v`, or `p -= lr · (g + β·v)` with Nesterov.
+#[derive(Debug, Clone)]
+pub struct Momentum {
+    pub learning_rate: f64,
+    pub beta: f64,
+    pub nesterov: bool,
+    velocity: Vec<f64>,
+}
+
+impl Momentum {
+    pub fn new(learning_rate: f64, beta: f64) -> Self {
+        Self {
+            learning_rate,
+            beta,
+            nesterov: false,
+            velocity: Vec::new(),
+        }
+    }
+
+    pub fn nesterov(learning_rate: f64, beta: f64) -> Self {
+        Self {
+            nesterov: true,
+            ..Self::new(learning_rate, beta)
+        }
+    }
+}
+
+impl Optimizer for Momentum {
+    fn step(&mut self, params: &mut [f64], grads: &[f64]) {
+        self.velocity.resize(params.len(), 0.0);
+
+        for ((p, g), v) in params.iter_mut().zip(grads).zip(self.velocity.iter_mut()) {
+            *v = self.beta * *v + g;
+            let update = if self.nesterov {
+                g + self.beta * *v
+            } else {
+                *v
+            };
+            *p -= self.learning_rate * update;
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        if self.nesterov {
+            "nesterov"
+        } else {
+            "momentum"
+        }
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn reset(&mut self) {
+        self.velocity.clear();
+    }
+}
+
+/// Scales each parameter's step by a running RMS of its gradients.
+///
+/// `s = ρ·s + (1 - ρ)·g²`, then `p -= lr · g / (√s + ε)`.
+#[derive(Debug, Clone)]
+pub struct RmsProp {
+    pub learning_rate: f64,
+    pub decay: f64,
+    pub epsilon: f64,
+    square_avg: Vec<f64>,
+}
+
+impl RmsProp {
+    pub fn new(learning_rate: f64) -> Self {
+        Self {
+            learning_rate,
+            decay: 0.9,
+            epsilon: 1e-8,
+            square_avg: Vec::new(),
+        }
+    }
+}
+
+impl Optimizer for RmsProp {
+    fn step(&mut self, params: &mut [f64], grads: &[f64]) {
+        self.square_avg.resize(params.len(), 0.0);
+
+        for ((p, g), s) in params.iter_mut().zip(grads).zip(self.square_avg.iter_mut()) {
+            *s = self.decay * *s + (1.0 - self.decay) * g * g;
+            *p -= self.learning_rate * g / (s.sqrt() + self.epsilon);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "rmsprop"
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn reset(&mut self) {
+        self.square_avg.clear();
+    }
+}
+
+/// Adam: momentum on the gradient plus RMSProp-style scaling, both
+/// bias-corrected for the first steps.
+#[derive(Debug, Clone)]
+pub struct Adam {
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    m: Vec<f64>,
+    v: Vec<f64>,
+    t: i32,
+}
+
+impl Adam {
+    pub fn new(learning_rate: f64) -> Self {
+        Self {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            m: Vec::new(),
+            v: Vec::new(),
+            t: 0,
+        }
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, params: &mut [f64], grads: &[f64]) {
+        self.m.resize(params.len(), 0.0);
+        self.v.resize(params.len(), 0.0);
+        self.t = self.t.saturating_add(1);
+
+        let m_correction = 1.0 - self.beta1.powi(self.t);
+        let v_correction = 1.0 - self.beta2.powi(self.t);
+
+        for (i, (p, g)) in params.iter_mut().zip(grads).enumerate() {
+            self.m[i] = self.beta1 * self.m[i] + (1.0 - self.beta1) * g;
+            self.v[i] = self.beta2 * self.v[i] + (1.0 - self.beta2) * g * g;
+
+            let m_hat = self.m[i] / m_correction;
+            let v_hat = self.v[i] / v_correction;
+            *p -= self.learning_rate * m_hat / (v_hat.sqrt() + self.epsilon);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "adam"
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn reset(&mut self) {
+        self.m.clear();
+        self.v.clear();
+        self.t = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(found: &[f64], expected: &[f64]) {
+        assert_eq!(found.len(), expected.len());
+        for (f, e) in found.iter().zip(expected) {
+            assert!((f - e).abs() < 1e-8, "{:?} != {:?}", found, expected);
+        }
+    }
+
+    #[test]
+    fn sgd_steps_against_the_gradient() {
+        let mut sgd = Sgd::new(0.1);
+        let mut params = [1.0, -2.0];
+        sgd.step(&mut params, &[0.5, -1.0]);
+        assert_close(&params, &[0.95, -1.9]);
+        sgd.step(&mut params, &[1.0, 1.0]);
+        assert_close(&params, &[0.85, -2.0]);
+        assert!(sgd.plain_steps());
+    }
+
+    #[test]
+    fn momentum_accumulates_velocity() {
+        let mut momentum = Momentum::new(0.1, 0.9);
+        let mut params = [1.0];
+        // v = 1
+        momentum.step(&mut params, &[1.0]);
+        assert_close(&params, &[0.9]);
+        // v = 0.9 · 1 + 1
+        momentum.step(&mut params, &[1.0]);
+        assert_close(&params, &[0.9 - 0.1 * 1.9]);
+        assert!(!momentum.plain_steps());
+    }
+
+    #[test]
+    fn nesterov_looks_ahead_along_the_velocity() {
+        let mut nesterov = Momentum::nesterov(0.1, 0.9);
+        let mut params = [1.0];
+        // v = 1, update g + β·v = 1.9
+        nesterov.step(&mut params, &[1.0]);
+        assert_close(&params, &[0.81]);
+        // v = 1.9, update 1 + 0.9 · 1.9 = 2.71
+        nesterov.step(&mut params, &[1.0]);
+        assert_close(&params, &[0.81 - 0.271]);
+        assert_eq!(nesterov.name(), "nesterov");
+    }
+
+    #[test]
+    fn rmsprop_scales_by_the_running_rms() {
+        let mut rmsprop = RmsProp::new(0.01);
+        let mut params = [1.0];
+        // s = 0.1 · 2²
+        rmsprop.step(&mut params, &[2.0]);
+        let first = 1.0 - 0.01 * 2.0 / 0.4f64.sqrt();
+        assert_close(&params, &[first]);
+        // s = 0.9 · 0.4 + 0.1 · 2²
+        rmsprop.step(&mut params, &[2.0]);
+        assert_close(&params, &[first - 0.01 * 2.0 / 0.76f64.sqrt()]);
+    }
+
+    #[test]
+    fn adam_corrects_the_bias_of_the_first_steps() {
+        let mut adam = Adam::new(0.1);
+        let mut params = [1.0, 1.0];
+        // m̂ = g and v̂ = g², so the first step is lr whatever the gradient's
+        // size; uncorrected it would be lr · 0.1·4 / √(0.001·16) ≈ 0.316
+        adam.step(&mut params, &[4.0, -1.0]);
+        assert_close(&params, &[0.9, 1.1]);
+
+        // m = 0.9·0.4 + 0.1·(-2), v = 0.999·0.016 + 0.001·4
+        adam.step(&mut params, &[-2.0, -1.0]);
+        let m_hat = 0.16 / (1.0 - 0.9f64.powi(2));
+        let v_hat = 0.019984 / (1.0 - 0.999f64.powi(2));
+        assert_close(&params, &[0.9 + 0.1 * -m_hat / v_hat.sqrt(), 1.2]);
+    }
+
+    #[test]
+    fn reset_forgets_the_state() {
+        let mut adam = Adam::new(0.1);
+        let mut params = [0.0];
+        adam.step(&mut params, &[4.0]);
+        adam.step(&mut params, &[-1.0]);
+        adam.reset();
+        let mut fresh = [0.0];
+        adam.step(&mut fresh, &[3.0]);
+        assert_close(&fresh, &[-0.1]);
+
+        let mut momentum = Momentum::new(0.1, 0.9);
+        momentum.step(&mut params, &[1.0]);
+        momentum.reset();
+        let mut fresh = [0.0];
+        momentum.step(&mut fresh, &[1.0]);
+        assert_close(&fresh, &[-0.1]);
+    }
+
+    #[test]
+    fn kinds_build_the_matching_optimizer() {
+        let kinds = [
+            (OptimizerKind::Sgd, "sgd"),
+            (OptimizerKind::Momentum { beta: 0.9 }, "momentum"),
+            (OptimizerKind::Nesterov { beta: 0.9 }, "nesterov"),
+            (OptimizerKind::RmsProp, "rmsprop"),
+            (OptimizerKind::Adam, "adam"),
+        ];
+        for (kind, name) in kinds {
+            let optimizer = kind.build(0.5);
+            assert_eq!(optimizer.name(), name);
+            assert_eq!(optimizer.learning_rate(), 0.5);
+        }
+    }
+}
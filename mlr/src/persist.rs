@@ -0,0 +1,483 @@
+//! Saving trained models to disk and loading them back for inference.
+//!
+//! A saved model is a [`ModelFile`]: the fitted parameters, the
+//! hyperparameters used to train them, metadata about the training data,
+//! and a format version. It can be written in two encodings.
+//!
+//! # JSON
+//!
+//! A single JSON object, e.g.
+//!
+//! ```text
+//! {
+//!   "format_version": 1,
+//!   "model": { "multi_linear": { "w": [1.5, -2.0], "b": 4.0 } },
+//!   "hyperparameters": { "epochs": "100000", "learning_rate": "0.01" },
+//!   "metadata": {
+//!     "feature_names": ["a", "b"],
+//!     "target_name": "y",
+//!     "n_samples": 200,
+//!     "final_loss": 0.0,
+//!     "trained_at": 1760745600,
+//!     "crate_version": "0.1.0"
+//!   }
+//! }
+//! ```
+//!
+//! `model` has exactly one key naming the model type (`linear` for
+//! `LinRegModel`, `multi_linear` for `MultiLinRegModel`, `polynomial` for
+//! `PolyRegModel`, `logistic` for `LogisticRegression`, `softmax` for
+//! `SoftmaxRegression` and `pipeline` for a `Pipeline`) whose value holds
+//! that model's parameters. A pipeline holds its fitted `steps` and a nested
+//! `model` object of the same form. New model types are only ever appended,
+//! so older files keep loading.
+//!
+//! JSON has no NaN or infinity, so non-finite numbers are written as `null`.
+//! A `null` `final_loss` reads back as `None`, but a model whose parameters
+//! diverged only round-trips through the binary format.
+//!
+//! # Binary
+//!
+//! | bytes | content                                                  |
+//! |-------|----------------------------------------------------------|
+//! | 0..4  | magic `b"MLRM"`                                          |
+//! | 4..8  | format version, `u32` little-endian                      |
+//! | 8..   | the same `ModelFile`, encoded with `bincode` 1.x defaults |
+//!
+//! The `bincode` payload repeats the version so both encodings decode to an
+//! identical `ModelFile`. Integers are little-endian fixed width, `f64`s are
+//! stored bit-exact, and strings/sequences are prefixed by a `u64` length.
+//!
+//! # Versioning
+//!
+//! [`FORMAT_VERSION`] is bumped whenever the layout changes incompatibly.
+//! Loading a file with a higher version than this build understands fails
+//! with `PersistError::UnsupportedVersion`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ModelError, PersistError};
+use crate::models::{
+    LinRegModel, LogisticRegression, MultiLinRegModel, Pipeline, PolyRegModel, Predict,
+    SoftmaxRegression,
+};
+
+pub const FORMAT_VERSION: u32 = 1;
+pub const BINARY_MAGIC: [u8; 4] = *b"MLRM";
+
+/// The fitted parameters of any model the crate can save, tagged by type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedModel {
+    Linear(LinRegModel),
+    MultiLinear(MultiLinRegModel),
+    Polynomial(PolyRegModel),
+    Logistic(LogisticRegression),
+    Softmax(SoftmaxRegression),
+    /// Feature transformations in front of any other saved model.
+    Pipeline(Box<Pipeline<SavedModel>>),
+}
+
+impl SavedModel {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            SavedModel::Linear(_) => "linear",
+            SavedModel::MultiLinear(_) => "multi_linear",
+            SavedModel::Polynomial(_) => "polynomial",
+            SavedModel::Logistic(_) => "logistic",
+            SavedModel::Softmax(_) => "softmax",
+            SavedModel::Pipeline(_) => "pipeline",
+        }
+    }
+
+    /// Whether the model predicts class indices rather than values.
+    pub fn is_classifier(&self) -> bool {
+        match self {
+            SavedModel::Logistic(_) | SavedModel::Softmax(_) => true,
+            SavedModel::Pipeline(pipeline) => pipeline.model.is_classifier(),
+            _ => false,
+        }
+    }
+
+    /// Predicts one value per row of `x`. `Linear` models read the first
+    /// column; classifiers return the class index as an `f64`.
+    pub fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, ModelError> {
+        let as_f64 = |labels: Vec<usize>| labels.into_iter().map(|c| c as f64).collect();
+        match self {
+            SavedModel::Linear(model) => Predict::predict(model, x),
+            SavedModel::MultiLinear(model) => Ok(model.predict(x)?),
+            SavedModel::Polynomial(model) => Ok(model.predict(x)?),
+            SavedModel::Logistic(model) => Ok(model.predict(x).map(as_f64)?),
+            SavedModel::Softmax(model) => Ok(model.predict(x).map(as_f64)?),
+            SavedModel::Pipeline(pipeline) => pipeline.predict(x),
+        }
+    }
+}
+
+impl Predict for SavedModel {
+    type Output = f64;
+
+    fn predict(&self, x: &[Vec<f64>]) -> Result<Vec<f64>, ModelError> {
+        SavedModel::predict(self, x)
+    }
+}
+
+impl From<LinRegModel> for SavedModel {
+    fn from(model: LinRegModel) -> Self {
+        SavedModel::Linear(model)
+    }
+}
+
+impl From<MultiLinRegModel> for SavedModel {
+    fn from(model: MultiLinRegModel) -> Self {
+        SavedModel::MultiLinear(model)
+    }
+}
+
+impl From<PolyRegModel> for SavedModel {
+    fn from(model: PolyRegModel) -> Self {
+        SavedModel::Polynomial(model)
+    }
+}
+
+impl From<LogisticRegression> for SavedModel {
+    fn from(model: LogisticRegression) -> Self {
+        SavedModel::Logistic(model)
+    }
+}
+
+impl From<SoftmaxRegression> for SavedModel {
+    fn from(model: SoftmaxRegression) -> Self {
+        SavedModel::Softmax(model)
+    }
+}
+
+impl<M: Into<SavedModel>> From<Pipeline<M>> for SavedModel {
+    fn from(pipeline: Pipeline<M>) -> Self {
+        SavedModel::Pipeline(Box::new(pipeline.map_model(Into::into)))
+    }
+}
+
+/// What the model was trained on, recorded for whoever loads it later.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TrainingMetadata {
+    /// Feature columns in the order the model expects them.
+    pub feature_names: Vec<String>,
+    pub target_name: String,
+    pub n_samples: usize,
+    /// Training-set loss after fitting, if it was computed: the MSE for
+    /// regressors, the cross-entropy for classifiers.
+    pub final_loss: Option<f64>,
+    /// Seconds since the Unix epoch when the file was created.
+    pub trained_at: u64,
+    /// Version of this crate that wrote the file.
+    pub crate_version: String,
+}
+
+impl TrainingMetadata {
+    /// Metadata stamped with the current time and crate version.
+    pub fn new(
+        feature_names: Vec<String>,
+        target_name: impl Into<String>,
+        n_samples: usize,
+    ) -> Self {
+        Self {
+            feature_names,
+            target_name: target_name.into(),
+            n_samples,
+            final_loss: None,
+            trained_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Everything written to a model file. See the module docs for the layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelFile {
+    pub format_version: u32,
+    pub model: SavedModel,
+    /// Hyperparameters by name, e.g. `"learning_rate" => "0.01"`. Values are
+    /// strings so every model type can record its own settings.
+    pub hyperparameters: BTreeMap<String, String>,
+    pub metadata: TrainingMetadata,
+}
+
+impl ModelFile {
+    pub fn new(model: impl Into<SavedModel>, metadata: TrainingMetadata) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            model: model.into(),
+            hyperparameters: BTreeMap::new(),
+            metadata,
+        }
+    }
+
+    /// Records a hyperparameter, formatted with `Display`.
+    pub fn with_hyperparameter(mut self, name: &str, value: impl ToString) -> Self {
+        self.hyperparameters
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn to_json(&self) -> Result<String, PersistError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, PersistError> {
+        let file: Self = serde_json::from_str(json)?;
+        check_version(file.format_version)?;
+        Ok(file)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PersistError> {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.extend_from_slice(&self.format_version.to_le_bytes());
+        bytes.extend(bincode::serialize(self)?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PersistError> {
+        if bytes.len() < 8 || bytes[..4] != BINARY_MAGIC {
+            return Err(PersistError::BadMagic);
+        }
+        let mut version = [0; 4];
+        version.copy_from_slice(&bytes[4..8]);
+        // Check the header first: a newer payload may not decode at all
+        check_version(u32::from_le_bytes(version))?;
+
+        let file: Self = bincode::deserialize(&bytes[8..])?;
+        check_version(file.format_version)?;
+        Ok(file)
+    }
+
+    /// Writes JSON if `path` ends in `.json`, the binary format otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PersistError> {
+        let path = path.as_ref();
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            fs::write(path, self.to_json()?)?;
+        } else {
+            fs::write(path, self.to_bytes()?)?;
+        }
+        Ok(())
+    }
+
+    /// Reads either encoding, telling them apart by the binary magic bytes.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PersistError> {
+        let bytes = fs::read(path)?;
+
+        if bytes.starts_with(&BINARY_MAGIC) {
+            Self::from_bytes(&bytes)
+        } else {
+            let json = String::from_utf8(bytes).map_err(|_| PersistError::BadMagic)?;
+            Self::from_json(&json)
+        }
+    }
+}
+
+fn check_version(found: u32) -> Result<(), PersistError> {
+    if found > FORMAT_VERSION {
+        return Err(PersistError::UnsupportedVersion {
+            found,
+            supported: FORMAT_VERSION,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preprocessing::{PolynomialFeatures, StandardScaler, Transformer};
+
+    fn metadata() -> TrainingMetadata {
+        TrainingMetadata {
+            feature_names: vec!["a".to_string(), "b".to_string()],
+            target_name: "y".to_string(),
+            n_samples: 3,
+            final_loss: Some(0.25),
+            trained_at: 1_760_745_600,
+            crate_version: "0.1.0".to_string(),
+        }
+    }
+
+    fn every_model() -> Vec<SavedModel> {
+        let mut scaler = StandardScaler::new();
+        scaler
+            .fit(&[vec![1.0, 2.0], vec![3.0, 6.0], vec![5.0, 7.0]])
+            .unwrap();
+        let multi = MultiLinRegModel {
+            w: vec![1.5, -2.0],
+            b: 4.0,
+        };
+
+        vec![
+            LinRegModel { w: 0.5, b: -1.25 }.into(),
+            multi.clone().into(),
+            PolyRegModel {
+                features: PolynomialFeatures::new(2).interaction_only(true),
+                linear: MultiLinRegModel {
+                    w: vec![0.1, 0.2, 0.3, 0.4],
+                    b: 0.0,
+                },
+            }
+            .into(),
+            LogisticRegression {
+                w: vec![0.75, -0.5],
+                b: 0.125,
+            }
+            .into(),
+            SoftmaxRegression {
+                w: vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![-1.0, -1.0]],
+                b: vec![0.0, 0.5, -0.5],
+            }
+            .into(),
+            Pipeline {
+                steps: vec![scaler.into()],
+                model: multi,
+            }
+            .into(),
+        ]
+    }
+
+    fn file(model: SavedModel) -> ModelFile {
+        ModelFile::new(model, metadata()).with_hyperparameter("learning_rate", 0.01)
+    }
+
+    #[test]
+    fn json_round_trips_every_model_type() {
+        for model in every_model() {
+            let name = model.type_name();
+            let original = file(model);
+            let json = original.to_json().unwrap();
+            assert!(json.contains(&format!("\"{}\"", name)), "{}", json);
+            assert_eq!(ModelFile::from_json(&json).unwrap(), original);
+        }
+    }
+
+    #[test]
+    fn binary_round_trips_every_model_type() {
+        for model in every_model() {
+            let original = file(model);
+            let bytes = original.to_bytes().unwrap();
+            assert_eq!(bytes[..4], BINARY_MAGIC);
+            assert_eq!(bytes[4..8], FORMAT_VERSION.to_le_bytes());
+            assert_eq!(ModelFile::from_bytes(&bytes).unwrap(), original);
+        }
+    }
+
+    #[test]
+    fn save_picks_the_encoding_from_the_extension() {
+        let dir = std::env::temp_dir();
+        let original = file(every_model().remove(5));
+        for name in ["mlr_persist_test.json", "mlr_persist_test.mlrm"] {
+            let path = dir.join(name);
+            original.save(&path).unwrap();
+            let is_binary = fs::read(&path).unwrap().starts_with(&BINARY_MAGIC);
+            assert_eq!(is_binary, name.ends_with(".mlrm"));
+            assert_eq!(ModelFile::load(&path).unwrap(), original);
+            fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn bad_magic_is_rejected() {
+        let mut bytes = file(every_model().remove(0)).to_bytes().unwrap();
+        bytes[0] = b'X';
+        assert!(matches!(
+            ModelFile::from_bytes(&bytes),
+            Err(PersistError::BadMagic)
+        ));
+        assert!(matches!(
+            ModelFile::from_bytes(b"MLRM"),
+            Err(PersistError::BadMagic)
+        ));
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let newer = FORMAT_VERSION + 1;
+        let unsupported = |result: Result<ModelFile, PersistError>| {
+            matches!(
+                result,
+                Err(PersistError::UnsupportedVersion { found, supported })
+                    if found == newer && supported == FORMAT_VERSION
+            )
+        };
+
+        let mut bytes = file(every_model().remove(0)).to_bytes().unwrap();
+        bytes[4..8].copy_from_slice(&newer.to_le_bytes());
+        assert!(unsupported(ModelFile::from_bytes(&bytes)));
+
+        let mut original = file(every_model().remove(0));
+        original.format_version = newer;
+        assert!(unsupported(ModelFile::from_json(
+            &original.to_json().unwrap()
+        )));
+        // The payload repeats the version, so a stale header doesn't hide it
+        let mut bytes = original.to_bytes().unwrap();
+        bytes[4..8].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        assert!(unsupported(ModelFile::from_bytes(&bytes)));
+    }
+
+    #[test]
+    fn truncated_binary_is_rejected() {
+        let bytes = file(every_model().remove(4)).to_bytes().unwrap();
+        for len in [bytes.len() - 1, bytes.len() / 2, 9] {
+            assert!(matches!(
+                ModelFile::from_bytes(&bytes[..len]),
+                Err(PersistError::Binary(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn non_finite_values_become_null_in_json() {
+        let mut original = file(
+            MultiLinRegModel {
+                w: vec![f64::NAN, 1.0],
+                b: f64::INFINITY,
+            }
+            .into(),
+        );
+        original.metadata.final_loss = Some(f64::NAN);
+        let json = original.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["model"]["multi_linear"]["w"][0].is_null());
+        assert!(value["model"]["multi_linear"]["b"].is_null());
+        assert!(value["metadata"]["final_loss"].is_null());
+
+        // A null loss reads back as "not computed", but a null weight can't
+        // be read back at all; only the binary format keeps them
+        assert!(matches!(
+            ModelFile::from_json(&json),
+            Err(PersistError::Json(_))
+        ));
+        original.model = every_model().remove(1);
+        let loaded = ModelFile::from_json(&original.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.metadata.final_loss, None);
+
+        let diverged = MultiLinRegModel {
+            w: vec![f64::NAN, f64::NEG_INFINITY],
+            b: f64::INFINITY,
+        };
+        let bytes = file(diverged.into()).to_bytes().unwrap();
+        let SavedModel::MultiLinear(loaded) = ModelFile::from_bytes(&bytes).unwrap().model else {
+            panic!("wrong model type");
+        };
+        assert!(loaded.w[0].is_nan());
+        assert_eq!(loaded.w[1], f64::NEG_INFINITY);
+        assert_eq!(loaded.b, f64::INFINITY);
+    }
+}
@@ -0,0 +1,23 @@
+//! Glob-import this module to bring the commonly used types into scope.
+
+pub use crate::dataset::{CsvOptions, Dataset};
+pub use crate::error::{
+    DatasetError, LinalgError, MetricsError, ModelError, ModelSelectionError, PersistError,
+    PreprocessingError,
+};
+pub use crate::linalg::{Matrix, Vector};
+pub use crate::model_selection::{CvScores, Fold, TrainTestSplit};
+pub use crate::models::{
+    Fit, LinRegModel, LogisticRegression, MultiLinRegModel, Pipeline, PipelineParams, PolyRegModel,
+    PolyRegParams, Predict, SoftmaxParams, SoftmaxRegression, Solver,
+};
+pub use crate::optim::{Adam, Momentum, Optimizer, OptimizerKind, RmsProp, Sgd};
+pub use crate::persist::ModelFile;
+pub use crate::preprocessing::{
+    FeatureTransform, HandleUnknown, ImputeStrategy, MinMaxScaler, Norm, Normalizer, OneHotEncoder,
+    OrdinalEncoder, PolynomialFeatures, RobustScaler, SimpleImputer, StandardScaler, Transformer,
+};
+pub use crate::regularization::Penalty;
+pub use crate::training::{
+    Batching, GradientDescent, StopReason, TrainConfig, TrainingHistory, TrainingReport,
+};
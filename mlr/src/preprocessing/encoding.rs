@@ -0,0 +1,473 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinalgError, PreprocessingError};
+use crate::preprocessing::{Transformer, check_width, column, n_columns};
+
+/// What an encoder does with a category it wasn't fitted on.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleUnknown {
+    /// Fail with `PreprocessingError::UnknownCategory`.
+    #[default]
+    Error,
+    /// Encode it as no known category: all zeros for `OneHotEncoder`,
+    /// `unknown_value` for `OrdinalEncoder`.
+    Ignore,
+}
+
+/// Replaces every categorical feature with one indicator column per
+/// category, `1` for the sample's category and `0` for the others.
+///
+/// Categories are the distinct values of each column in `columns`, such as
+/// the codes `Dataset` gives text categories, together with every code
+/// named in `category_names`; the other features pass through in place.
+/// Naming the codes of all of `Dataset::categories` thus keeps a category
+/// known even when the rows being fitted, such as a cross-validation fold,
+/// have none of it. `NaN` is never a category, so impute missing values
+/// first.
+///
+/// Every model in this crate fits its own intercept, and the indicators of
+/// a feature always sum to 1, so with all of them the closed-form fit is
+/// singular. `drop_first` leaves out the indicator of the first category,
+/// which the intercept then stands for; an unknown category ignored under
+/// `HandleUnknown::Ignore` is then encoded like the first one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OneHotEncoder {
+    /// Positions of the categorical features, each listed once.
+    pub columns: Vec<usize>,
+    pub handle_unknown: HandleUnknown,
+    /// Leave out the indicator column of each feature's first category.
+    pub drop_first: bool,
+    /// Optional names for the codes of every column in `columns`:
+    /// `category_names[k][c]` names code `c`, which `fit` then takes as a
+    /// category. Used by `feature_names`, and saved so text can be coded
+    /// the same way again.
+    pub category_names: Vec<Vec<String>>,
+    /// Sorted categories of every column in `columns`, learned by `fit`.
+    pub categories: Vec<Vec<f64>>,
+    /// Number of input features seen by `fit`.
+    pub n_features: usize,
+}
+
+impl OneHotEncoder {
+    pub fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            handle_unknown: HandleUnknown::Error,
+            drop_first: false,
+            category_names: Vec::new(),
+            categories: Vec::new(),
+            n_features: 0,
+        }
+    }
+
+    pub fn handle_unknown(mut self, handle_unknown: HandleUnknown) -> Self {
+        self.handle_unknown = handle_unknown;
+        self
+    }
+
+    pub fn drop_first(mut self, drop_first: bool) -> Self {
+        self.drop_first = drop_first;
+        self
+    }
+
+    pub fn category_names(mut self, category_names: Vec<Vec<String>>) -> Self {
+        self.category_names = category_names;
+        self
+    }
+
+    /// Number of output columns: one per encoded category of every
+    /// categorical feature, plus the features passed through.
+    pub fn n_output_features(&self) -> usize {
+        let n_indicators: usize = (0..self.categories.len())
+            .map(|k| self.block_width(k))
+            .sum();
+        self.n_features - self.columns.len() + n_indicators
+    }
+
+    // Number of indicator columns of categorical feature `k`.
+    fn block_width(&self, k: usize) -> usize {
+        self.categories[k].len() - usize::from(self.drop_first && !self.categories[k].is_empty())
+    }
+
+    // Index of the first category that gets an indicator column.
+    fn first_encoded(&self) -> usize {
+        usize::from(self.drop_first)
+    }
+
+    /// Names of the output columns, e.g. `"color=red"` for an indicator
+    /// column, or `"color=2"` when the code has no name.
+    pub fn feature_names(&self, input_names: &[String]) -> Vec<String> {
+        let slots = slots(&self.columns, self.n_features);
+        let mut names = Vec::with_capacity(self.n_output_features());
+        for (j, name) in input_names.iter().enumerate() {
+            match slots.get(j).copied().flatten() {
+                Some(k) => {
+                    for &category in self.categories[k].iter().skip(self.first_encoded()) {
+                        let label = category_name(&self.category_names, k, category)
+                            .map_or_else(|| category.to_string(), str::to_string);
+                        names.push(format!("{}={}", name, label));
+                    }
+                }
+                None => names.push(name.clone()),
+            }
+        }
+        names
+    }
+}
+
+impl Transformer for OneHotEncoder {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        self.categories = fit_categories(x, &self.columns, &self.category_names)?;
+        self.n_features = x[0].len();
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        check_width(x, self.n_features)?;
+        let slots = slots(&self.columns, self.n_features);
+        x.iter()
+            .map(|row| {
+                let mut encoded = Vec::with_capacity(self.n_output_features());
+                for (j, &value) in row.iter().enumerate() {
+                    let Some(k) = slots[j] else {
+                        encoded.push(value);
+                        continue;
+                    };
+                    let categories = &self.categories[k];
+                    let code = find(categories, value);
+                    if code.is_none() && self.handle_unknown == HandleUnknown::Error {
+                        return Err(PreprocessingError::UnknownCategory { column: j, value });
+                    }
+                    encoded.extend(
+                        (self.first_encoded()..categories.len())
+                            .map(|c| f64::from(code == Some(c))),
+                    );
+                }
+                Ok(encoded)
+            })
+            .collect()
+    }
+
+    /// Takes the largest indicator of every block as the category. An
+    /// all-zero block, which an unknown category gives, becomes the first
+    /// category under `drop_first` and `NaN` otherwise.
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        check_width(x, self.n_output_features())?;
+        let slots = slots(&self.columns, self.n_features);
+        Ok(x.iter()
+            .map(|row| {
+                let mut rest = row.as_slice();
+                slots
+                    .iter()
+                    .map(|slot| {
+                        let width = slot.map_or(1, |k| self.block_width(k));
+                        let (block, tail) = rest.split_at(width);
+                        rest = tail;
+                        match *slot {
+                            None => block[0],
+                            Some(k) => {
+                                let dropped = self.categories[k]
+                                    .first()
+                                    .copied()
+                                    .filter(|_| self.drop_first);
+                                block
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|&(_, &v)| v > 0.0)
+                                    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                                    .map(|(c, _)| self.categories[k][c + self.first_encoded()])
+                                    .or(dropped)
+                                    .unwrap_or(f64::NAN)
+                            }
+                        }
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// Replaces every categorical feature with the rank of its category among
+/// the sorted categories seen by `fit`, or named in `category_names`:
+/// `0, 1, 2, ...`.
+///
+/// This keeps one column per feature, but a linear model then treats the
+/// categories as evenly spaced and ordered, so it suits categories with a
+/// natural order, such as sizes. Like `OneHotEncoder`, it encodes the
+/// features at `columns` and passes the others through.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrdinalEncoder {
+    /// Positions of the categorical features, each listed once.
+    pub columns: Vec<usize>,
+    pub handle_unknown: HandleUnknown,
+    /// The code given to unknown categories under `HandleUnknown::Ignore`,
+    /// `-1` by default.
+    pub unknown_value: f64,
+    /// Optional names for the codes of every column in `columns`, which
+    /// `fit` takes as categories, as in `OneHotEncoder`.
+    pub category_names: Vec<Vec<String>>,
+    /// Sorted categories of every column in `columns`, learned by `fit`.
+    pub categories: Vec<Vec<f64>>,
+    /// Number of input features seen by `fit`.
+    pub n_features: usize,
+}
+
+impl OrdinalEncoder {
+    pub fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            handle_unknown: HandleUnknown::Error,
+            unknown_value: -1.0,
+            category_names: Vec::new(),
+            categories: Vec::new(),
+            n_features: 0,
+        }
+    }
+
+    pub fn handle_unknown(mut self, handle_unknown: HandleUnknown) -> Self {
+        self.handle_unknown = handle_unknown;
+        self
+    }
+
+    pub fn unknown_value(mut self, unknown_value: f64) -> Self {
+        self.unknown_value = unknown_value;
+        self
+    }
+
+    pub fn category_names(mut self, category_names: Vec<Vec<String>>) -> Self {
+        self.category_names = category_names;
+        self
+    }
+}
+
+impl Transformer for OrdinalEncoder {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        self.categories = fit_categories(x, &self.columns, &self.category_names)?;
+        self.n_features = x[0].len();
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        check_width(x, self.n_features)?;
+        let slots = slots(&self.columns, self.n_features);
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&slots)
+                    .enumerate()
+                    .map(|(j, (&value, slot))| match *slot {
+                        None => Ok(value),
+                        Some(k) => match find(&self.categories[k], value) {
+                            Some(code) => Ok(code as f64),
+                            None if self.handle_unknown == HandleUnknown::Ignore => {
+                                Ok(self.unknown_value)
+                            }
+                            None => Err(PreprocessingError::UnknownCategory { column: j, value }),
+                        },
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Maps ranks back to categories; anything that isn't a rank of a known
+    /// category becomes `NaN`.
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        check_width(x, self.n_features)?;
+        let slots = slots(&self.columns, self.n_features);
+        Ok(x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&slots)
+                    .map(|(&value, slot)| match *slot {
+                        None => value,
+                        Some(k) if value >= 0.0 && value.fract() == 0.0 => self.categories[k]
+                            .get(value as usize)
+                            .copied()
+                            .unwrap_or(f64::NAN),
+                        Some(_) => f64::NAN,
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+// The sorted distinct values of every column in `columns`, leaving out NaN,
+// and the codes that have a name in `names`.
+fn fit_categories(
+    x: &[Vec<f64>],
+    columns: &[usize],
+    names: &[Vec<String>],
+) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+    let d = n_columns(x)?;
+    columns
+        .iter()
+        .enumerate()
+        .map(|(k, &j)| {
+            if j >= d {
+                return Err(LinalgError::DimensionMismatch {
+                    expected: j + 1,
+                    found: d,
+                }
+                .into());
+            }
+            if columns[..k].contains(&j) {
+                return Err(PreprocessingError::DuplicateColumn { column: j });
+            }
+            let named = names.get(k).map_or(0, Vec::len);
+            let mut values: Vec<f64> = column(x, j)
+                .filter(|v| !v.is_nan())
+                .chain((0..named).map(|code| code as f64))
+                .collect();
+            values.sort_by(f64::total_cmp);
+            values.dedup();
+            Ok(values)
+        })
+        .collect()
+}
+
+// For every input feature, its position in `columns` if it is categorical.
+fn slots(columns: &[usize], n_features: usize) -> Vec<Option<usize>> {
+    let mut slots = vec![None; n_features];
+    for (k, &j) in columns.iter().enumerate() {
+        if let Some(slot) = slots.get_mut(j) {
+            *slot = Some(k);
+        }
+    }
+    slots
+}
+
+fn find(categories: &[f64], value: f64) -> Option<usize> {
+    categories.binary_search_by(|c| c.total_cmp(&value)).ok()
+}
+
+fn category_name(names: &[Vec<String>], k: usize, code: f64) -> Option<&str> {
+    if code < 0.0 || code.fract() != 0.0 {
+        return None;
+    }
+    names.get(k)?.get(code as usize).map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A numeric feature, then a categorical one with codes 0..3
+    fn data() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.5, 2.0],
+            vec![-1.0, 0.0],
+            vec![0.5, 1.0],
+            vec![2.5, 2.0],
+        ]
+    }
+
+    #[test]
+    fn one_hot_encodes_in_place() {
+        let mut encoder = OneHotEncoder::new(vec![1]);
+        let encoded = encoder.fit_transform(&data()).unwrap();
+        assert_eq!(encoder.n_output_features(), 4);
+        assert_eq!(encoded[0], [1.5, 0.0, 0.0, 1.0]);
+        assert_eq!(encoded[1], [-1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(encoder.inverse_transform(&encoded).unwrap(), data());
+    }
+
+    #[test]
+    fn drop_first_leaves_out_one_column_per_feature() {
+        let x = vec![
+            vec![0.0, 5.0, 1.0],
+            vec![1.0, 6.0, 0.0],
+            vec![2.0, 7.0, 1.0],
+        ];
+        let mut full = OneHotEncoder::new(vec![0, 2]);
+        full.fit(&x).unwrap();
+        assert_eq!(full.n_output_features(), 3 + 1 + 2);
+
+        let mut dropped = OneHotEncoder::new(vec![0, 2]).drop_first(true);
+        let encoded = dropped.fit_transform(&x).unwrap();
+        assert_eq!(dropped.n_output_features(), 2 + 1 + 1);
+        assert!(encoded.iter().all(|row| row.len() == 4));
+        assert_eq!(encoded[0], [0.0, 0.0, 5.0, 1.0]);
+        assert_eq!(dropped.inverse_transform(&encoded).unwrap(), x);
+    }
+
+    #[test]
+    fn unknown_categories_fail_or_are_ignored() {
+        let unseen = vec![vec![0.0, 7.0]];
+        let unknown = Err(PreprocessingError::UnknownCategory {
+            column: 1,
+            value: 7.0,
+        });
+
+        let mut one_hot = OneHotEncoder::new(vec![1]);
+        one_hot.fit(&data()).unwrap();
+        assert_eq!(one_hot.transform(&unseen), unknown);
+        let mut ordinal = OrdinalEncoder::new(vec![1]);
+        ordinal.fit(&data()).unwrap();
+        assert_eq!(ordinal.transform(&unseen), unknown);
+
+        let mut one_hot = OneHotEncoder::new(vec![1]).handle_unknown(HandleUnknown::Ignore);
+        one_hot.fit(&data()).unwrap();
+        assert_eq!(one_hot.transform(&unseen).unwrap(), [[0.0, 0.0, 0.0, 0.0]]);
+        let mut ordinal = OrdinalEncoder::new(vec![1])
+            .handle_unknown(HandleUnknown::Ignore)
+            .unknown_value(-5.0);
+        ordinal.fit(&data()).unwrap();
+        assert_eq!(ordinal.transform(&unseen).unwrap(), [[0.0, -5.0]]);
+    }
+
+    #[test]
+    fn named_codes_are_categories_even_when_unseen() {
+        let names = vec![
+            vec!["a", "b", "c", "d"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        ];
+        let mut encoder = OneHotEncoder::new(vec![1]).category_names(names.clone());
+        encoder.fit(&data()).unwrap();
+        assert_eq!(encoder.categories, [[0.0, 1.0, 2.0, 3.0]]);
+        assert_eq!(
+            encoder.transform(&[vec![0.0, 3.0]]).unwrap(),
+            [[0.0, 0.0, 0.0, 0.0, 1.0]]
+        );
+        assert_eq!(
+            encoder.feature_names(&["x".to_string(), "letter".to_string()]),
+            ["x", "letter=a", "letter=b", "letter=c", "letter=d"]
+        );
+
+        let mut ordinal = OrdinalEncoder::new(vec![1]).category_names(names);
+        ordinal.fit(&data()).unwrap();
+        assert_eq!(ordinal.transform(&[vec![0.0, 3.0]]).unwrap(), [[0.0, 3.0]]);
+    }
+
+    #[test]
+    fn ordinal_ranks_sorted_categories() {
+        let x = vec![vec![10.0], vec![-3.0], vec![4.0], vec![10.0]];
+        let mut encoder = OrdinalEncoder::new(vec![0]);
+        let encoded = encoder.fit_transform(&x).unwrap();
+        assert_eq!(encoded, [[2.0], [0.0], [1.0], [2.0]]);
+        assert_eq!(encoder.inverse_transform(&encoded).unwrap(), x);
+        assert!(encoder.inverse_transform(&[vec![0.5]]).unwrap()[0][0].is_nan());
+    }
+
+    #[test]
+    fn fit_rejects_a_column_listed_twice() {
+        let duplicate = Err(PreprocessingError::DuplicateColumn { column: 1 });
+        assert_eq!(OneHotEncoder::new(vec![1, 0, 1]).fit(&data()), duplicate);
+        assert_eq!(OrdinalEncoder::new(vec![1, 1]).fit(&data()), duplicate);
+    }
+
+    #[test]
+    fn fit_rejects_a_column_past_the_features() {
+        assert_eq!(
+            OneHotEncoder::new(vec![2]).fit(&data()),
+            Err(PreprocessingError::Linalg(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }))
+        );
+    }
+}
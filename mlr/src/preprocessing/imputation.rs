@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinalgError, PreprocessingError};
+use crate::preprocessing::{Transformer, check_width, column, n_columns};
+
+/// The value `SimpleImputer` fills the gaps of a feature with.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImputeStrategy {
+    /// Mean of the observed values.
+    #[default]
+    Mean,
+    /// Median of the observed values, which outliers don't pull away.
+    Median,
+    /// The most common observed value, the smallest on a tie. Suits
+    /// categorical features, whose codes mean and median would mix.
+    MostFrequent,
+    /// The same value for every feature.
+    Constant(f64),
+}
+
+impl ImputeStrategy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImputeStrategy::Mean => "mean",
+            ImputeStrategy::Median => "median",
+            ImputeStrategy::MostFrequent => "most_frequent",
+            ImputeStrategy::Constant(_) => "constant",
+        }
+    }
+}
+
+/// Fills missing values, stored as `NaN` (see
+/// `dataset::MissingValues::Keep`), with a per-feature statistic of the
+/// observed training values.
+///
+/// The features listed in `categorical` hold category codes, which a mean
+/// or median would fill with a code between two categories; under `Mean`
+/// and `Median` they get the most frequent code instead.
+///
+/// Fitting fails with `LinalgError::Empty` if a feature has no observed
+/// values and the strategy needs some. Filled values can't be told apart
+/// from observed ones afterwards, so `inverse_transform` fails with
+/// `PreprocessingError::NotInvertible`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SimpleImputer {
+    pub strategy: ImputeStrategy,
+    /// Positions of the features that are category codes.
+    pub categorical: Vec<usize>,
+    /// The fill value of every feature, learned by `fit`.
+    pub statistics: Vec<f64>,
+}
+
+impl SimpleImputer {
+    pub fn new(strategy: ImputeStrategy) -> Self {
+        Self {
+            strategy,
+            categorical: Vec::new(),
+            statistics: Vec::new(),
+        }
+    }
+
+    pub fn categorical(mut self, categorical: Vec<usize>) -> Self {
+        self.categorical = categorical;
+        self
+    }
+}
+
+impl Transformer for SimpleImputer {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        let d = n_columns(x)?;
+        self.statistics = (0..d)
+            .map(|j| {
+                let strategy = match self.strategy {
+                    ImputeStrategy::Mean | ImputeStrategy::Median
+                        if self.categorical.contains(&j) =>
+                    {
+                        ImputeStrategy::MostFrequent
+                    }
+                    strategy => strategy,
+                };
+                if let ImputeStrategy::Constant(value) = strategy {
+                    return Ok(value);
+                }
+                let mut observed: Vec<f64> = column(x, j).filter(|v| !v.is_nan()).collect();
+                if observed.is_empty() {
+                    return Err(LinalgError::Empty);
+                }
+                observed.sort_by(f64::total_cmp);
+                let n = observed.len();
+                Ok(match strategy {
+                    ImputeStrategy::Mean => observed.iter().sum::<f64>() / n as f64,
+                    ImputeStrategy::Median if n % 2 == 1 => observed[n / 2],
+                    ImputeStrategy::Median => (observed[n / 2 - 1] + observed[n / 2]) / 2.0,
+                    ImputeStrategy::MostFrequent => most_frequent(&observed),
+                    ImputeStrategy::Constant(value) => value,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        check_width(x, self.statistics.len())?;
+        Ok(x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.statistics)
+                    .map(|(&v, &fill)| if v.is_nan() { fill } else { v })
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn inverse_transform(&self, _: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        Err(PreprocessingError::NotInvertible)
+    }
+}
+
+// The most common of sorted, non-empty `values`, the smallest on a tie.
+fn most_frequent(values: &[f64]) -> f64 {
+    let mut best = (values[0], 0);
+    for run in values.chunk_by(|a, b| a == b) {
+        if run.len() > best.1 {
+            best = (run[0], run.len());
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAN: f64 = f64::NAN;
+
+    fn data() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 2.0, NAN],
+            vec![NAN, 2.0, 0.0],
+            vec![3.0, NAN, 1.0],
+            vec![10.0, 5.0, 1.0],
+        ]
+    }
+
+    fn statistics(imputer: SimpleImputer) -> Vec<f64> {
+        let mut imputer = imputer;
+        imputer.fit(&data()).unwrap();
+        imputer.statistics
+    }
+
+    #[test]
+    fn statistics_ignore_missing_values() {
+        assert_eq!(
+            statistics(SimpleImputer::new(ImputeStrategy::Mean)),
+            [14.0 / 3.0, 3.0, 2.0 / 3.0]
+        );
+        assert_eq!(
+            statistics(SimpleImputer::new(ImputeStrategy::Median)),
+            [3.0, 2.0, 1.0]
+        );
+        assert_eq!(
+            statistics(SimpleImputer::new(ImputeStrategy::MostFrequent)),
+            [1.0, 2.0, 1.0]
+        );
+        assert_eq!(
+            statistics(SimpleImputer::new(ImputeStrategy::Constant(-1.0))),
+            [-1.0, -1.0, -1.0]
+        );
+    }
+
+    #[test]
+    fn categorical_features_get_the_most_frequent_code() {
+        let imputer = SimpleImputer::new(ImputeStrategy::Mean).categorical(vec![2]);
+        assert_eq!(statistics(imputer), [14.0 / 3.0, 3.0, 1.0]);
+        let imputer = SimpleImputer::new(ImputeStrategy::Constant(7.0)).categorical(vec![2]);
+        assert_eq!(statistics(imputer), [7.0, 7.0, 7.0]);
+    }
+
+    #[test]
+    fn transform_fills_only_the_gaps() {
+        let mut imputer = SimpleImputer::new(ImputeStrategy::Median);
+        let filled = imputer.fit_transform(&data()).unwrap();
+        assert_eq!(
+            filled,
+            [
+                [1.0, 2.0, 1.0],
+                [3.0, 2.0, 0.0],
+                [3.0, 2.0, 1.0],
+                [10.0, 5.0, 1.0]
+            ]
+        );
+        assert_eq!(
+            imputer.inverse_transform(&filled),
+            Err(PreprocessingError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn fit_fails_on_a_feature_with_no_values() {
+        let x = vec![vec![1.0, NAN], vec![2.0, NAN]];
+        assert_eq!(
+            SimpleImputer::new(ImputeStrategy::Mean).fit(&x),
+            Err(PreprocessingError::Linalg(LinalgError::Empty))
+        );
+        assert!(
+            SimpleImputer::new(ImputeStrategy::Constant(0.0))
+                .fit(&x)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Transformations applied to features before they reach a model: scaling,
+//! encoding categories and filling in missing values.
+//!
+//! Transformations that learn from the training data implement
+//! [`Transformer`]. [`FeatureTransform`] holds any of them, so a list of
+//! steps can be fitted, saved and replayed in front of a model; see
+//! `models::Pipeline`.
+
+pub mod encoding;
+pub mod imputation;
+pub mod polynomial;
+pub mod scaling;
+
+pub use encoding::{HandleUnknown, OneHotEncoder, OrdinalEncoder};
+pub use imputation::{ImputeStrategy, SimpleImputer};
+pub use polynomial::PolynomialFeatures;
+pub use scaling::{MinMaxScaler, Norm, Normalizer, RobustScaler, StandardScaler};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinalgError, PreprocessingError};
+use crate::linalg::matrix_functions::matrix_shape;
+
+/// A transformation of `n × d` feature rows whose parameters are learned by
+/// `fit` and then applied unchanged to any later data.
+///
+/// Transformers that learn per-feature statistics fail with
+/// `DimensionMismatch` in `transform` and `inverse_transform` when a row
+/// doesn't have the number of features seen by `fit`, including when `fit`
+/// was never called.
+pub trait Transformer {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError>;
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError>;
+
+    /// Maps transformed rows back to the original units.
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError>;
+
+    fn fit_transform(&mut self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+}
+
+/// Any transformation in this module, tagged by type so it can be saved
+/// alongside a model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureTransform {
+    Standard(StandardScaler),
+    MinMax(MinMaxScaler),
+    Robust(RobustScaler),
+    Normalizer(Normalizer),
+    OneHot(OneHotEncoder),
+    Ordinal(OrdinalEncoder),
+    Imputer(SimpleImputer),
+}
+
+impl FeatureTransform {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            FeatureTransform::Standard(_) => "standard",
+            FeatureTransform::MinMax(_) => "min_max",
+            FeatureTransform::Robust(_) => "robust",
+            FeatureTransform::Normalizer(_) => "normalizer",
+            FeatureTransform::OneHot(_) => "one_hot",
+            FeatureTransform::Ordinal(_) => "ordinal",
+            FeatureTransform::Imputer(_) => "imputer",
+        }
+    }
+
+    fn as_transformer(&self) -> &dyn Transformer {
+        match self {
+            FeatureTransform::Standard(t) => t,
+            FeatureTransform::MinMax(t) => t,
+            FeatureTransform::Robust(t) => t,
+            FeatureTransform::Normalizer(t) => t,
+            FeatureTransform::OneHot(t) => t,
+            FeatureTransform::Ordinal(t) => t,
+            FeatureTransform::Imputer(t) => t,
+        }
+    }
+
+    fn as_transformer_mut(&mut self) -> &mut dyn Transformer {
+        match self {
+            FeatureTransform::Standard(t) => t,
+            FeatureTransform::MinMax(t) => t,
+            FeatureTransform::Robust(t) => t,
+            FeatureTransform::Normalizer(t) => t,
+            FeatureTransform::OneHot(t) => t,
+            FeatureTransform::Ordinal(t) => t,
+            FeatureTransform::Imputer(t) => t,
+        }
+    }
+}
+
+impl Transformer for FeatureTransform {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        self.as_transformer_mut().fit(x)
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        self.as_transformer().transform(x)
+    }
+
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        self.as_transformer().inverse_transform(x)
+    }
+}
+
+impl From<StandardScaler> for FeatureTransform {
+    fn from(scaler: StandardScaler) -> Self {
+        FeatureTransform::Standard(scaler)
+    }
+}
+
+impl From<MinMaxScaler> for FeatureTransform {
+    fn from(scaler: MinMaxScaler) -> Self {
+        FeatureTransform::MinMax(scaler)
+    }
+}
+
+impl From<RobustScaler> for FeatureTransform {
+    fn from(scaler: RobustScaler) -> Self {
+        FeatureTransform::Robust(scaler)
+    }
+}
+
+impl From<Normalizer> for FeatureTransform {
+    fn from(normalizer: Normalizer) -> Self {
+        FeatureTransform::Normalizer(normalizer)
+    }
+}
+
+impl From<OneHotEncoder> for FeatureTransform {
+    fn from(encoder: OneHotEncoder) -> Self {
+        FeatureTransform::OneHot(encoder)
+    }
+}
+
+impl From<OrdinalEncoder> for FeatureTransform {
+    fn from(encoder: OrdinalEncoder) -> Self {
+        FeatureTransform::Ordinal(encoder)
+    }
+}
+
+impl From<SimpleImputer> for FeatureTransform {
+    fn from(imputer: SimpleImputer) -> Self {
+        FeatureTransform::Imputer(imputer)
+    }
+}
+
+// Number of features in the training rows `x`, which must be rectangular
+// and non-empty.
+fn n_columns(x: &[Vec<f64>]) -> Result<usize, LinalgError> {
+    match matrix_shape(x)? {
+        (0, _) | (_, 0) => Err(LinalgError::Empty),
+        (_, cols) => Ok(cols),
+    }
+}
+
+// Every row of `x` must have the `expected` number of features.
+fn check_width(x: &[Vec<f64>], expected: usize) -> Result<(), LinalgError> {
+    match x.iter().find(|row| row.len() != expected) {
+        Some(row) => Err(LinalgError::DimensionMismatch {
+            expected,
+            found: row.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn column(x: &[Vec<f64>], j: usize) -> impl Iterator<Item = f64> + '_ {
+    x.iter().map(move |row| row[j])
+}
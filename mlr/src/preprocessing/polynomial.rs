@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::LinalgError;
+use crate::linalg::matrix_functions::matrix_shape;
+
+/// Expands every sample into all products of its features up to `degree`,
+/// so a linear model on the result fits a polynomial in the inputs.
+///
+/// Terms are ordered by degree, then lexicographically by feature index:
+/// for two features `a, b` and degree 2 the output is
+/// `[a, b, a², a·b, b²]`, preceded by a constant `1` if `include_bias` is set.
+///
+/// The bias column is off by default because every model in this crate
+/// already fits its own intercept; with both, the closed-form fit would be
+/// singular.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolynomialFeatures {
+    pub degree: usize,
+    /// Only keep products of distinct features (`a·b`, not `a²`).
+    pub interaction_only: bool,
+    /// Prepend a constant column of ones.
+    pub include_bias: bool,
+}
+
+impl PolynomialFeatures {
+    pub fn new(degree: usize) -> Self {
+        Self {
+            degree,
+            interaction_only: false,
+            include_bias: false,
+        }
+    }
+
+    pub fn interaction_only(mut self, interaction_only: bool) -> Self {
+        self.interaction_only = interaction_only;
+        self
+    }
+
+    pub fn include_bias(mut self, include_bias: bool) -> Self {
+        self.include_bias = include_bias;
+        self
+    }
+
+    /// The output terms for `n_features` inputs, each given as the indices of
+    /// the input features it multiplies (`[0, 0, 1]` is `x0²·x1`, `[]` the bias).
+    pub fn terms(&self, n_features: usize) -> Vec<Vec<usize>> {
+        let mut terms = Vec::new();
+        if self.include_bias {
+            terms.push(Vec::new());
+        }
+
+        for degree in 1..=self.degree {
+            let mut term = Vec::with_capacity(degree);
+            self.push_terms(n_features, degree, 0, &mut term, &mut terms);
+        }
+        terms
+    }
+
+    // Appends every term of `degree` factors that extends `term` with
+    // feature indices of at least `first`, keeping the indices sorted.
+    fn push_terms(
+        &self,
+        n_features: usize,
+        degree: usize,
+        first: usize,
+        term: &mut Vec<usize>,
+        terms: &mut Vec<Vec<usize>>,
+    ) {
+        if term.len() == degree {
+            terms.push(term.clone());
+            return;
+        }
+
+        for j in first..n_features {
+            term.push(j);
+            let next = if self.interaction_only { j + 1 } else { j };
+            self.push_terms(n_features, degree, next, term, terms);
+            term.pop();
+        }
+    }
+
+    /// Number of output columns for `n_features` inputs.
+    pub fn n_output_features(&self, n_features: usize) -> usize {
+        self.terms(n_features).len()
+    }
+
+    /// Names of the output columns, e.g. `"a^2 b"` for `a²·b` and `"1"` for
+    /// the bias.
+    pub fn feature_names(&self, input_names: &[String]) -> Vec<String> {
+        self.terms(input_names.len())
+            .iter()
+            .map(|term| {
+                if term.is_empty() {
+                    return "1".to_string();
+                }
+                let mut factors: Vec<String> = Vec::new();
+                let mut rest = term.as_slice();
+                while let Some(&j) = rest.first() {
+                    let power = rest.iter().take_while(|&&k| k == j).count();
+                    factors.push(match power {
+                        1 => input_names[j].clone(),
+                        _ => format!("{}^{}", input_names[j], power),
+                    });
+                    rest = &rest[power..];
+                }
+                factors.join(" ")
+            })
+            .collect()
+    }
+
+    /// Expands every row of `x`. All rows must have the same length.
+    pub fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, LinalgError> {
+        let (_, n_features) = matrix_shape(x)?;
+        let terms = self.terms(n_features);
+        Ok(x.iter()
+            .map(|row| {
+                terms
+                    .iter()
+                    .map(|term| term.iter().map(|&j| row[j]).product())
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Expands a single feature given as one value per sample, the layout
+    /// `LinRegModel` takes.
+    pub fn transform_column(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let terms = self.terms(1);
+        x.iter()
+            .map(|&x_i| {
+                terms
+                    .iter()
+                    .map(|term| x_i.powi(term.len() as i32))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn terms_are_ordered_by_degree_then_feature() {
+        let features = PolynomialFeatures::new(2);
+        assert_eq!(
+            features.terms(2),
+            [vec![0], vec![1], vec![0, 0], vec![0, 1], vec![1, 1]]
+        );
+        assert_eq!(
+            features.feature_names(&names(&["a", "b"])),
+            ["a", "b", "a^2", "a b", "b^2"]
+        );
+        assert_eq!(
+            PolynomialFeatures::new(3).feature_names(&names(&["a", "b"]))[5..],
+            ["a^3", "a^2 b", "a b^2", "b^3"]
+        );
+    }
+
+    #[test]
+    fn term_count_is_a_binomial_coefficient() {
+        // C(n + d, d) - 1 monomials of degree 1 to d in n variables
+        for (n_features, degree, count) in [(1, 3, 3), (2, 2, 5), (3, 2, 9), (3, 3, 19), (4, 2, 14)]
+        {
+            let features = PolynomialFeatures::new(degree);
+            assert_eq!(features.n_output_features(n_features), count);
+            assert_eq!(
+                features.include_bias(true).n_output_features(n_features),
+                count + 1
+            );
+        }
+        assert_eq!(PolynomialFeatures::new(0).n_output_features(3), 0);
+    }
+
+    #[test]
+    fn interaction_only_skips_powers() {
+        let features = PolynomialFeatures::new(3).interaction_only(true);
+        assert_eq!(
+            features.feature_names(&names(&["a", "b", "c"])),
+            ["a", "b", "c", "a b", "a c", "b c", "a b c"]
+        );
+        // No product of distinct features has more factors than features
+        assert_eq!(
+            PolynomialFeatures::new(5)
+                .interaction_only(true)
+                .n_output_features(2),
+            3
+        );
+    }
+
+    #[test]
+    fn bias_column_comes_first() {
+        let features = PolynomialFeatures::new(2).include_bias(true);
+        assert_eq!(features.terms(1), [vec![], vec![0], vec![0, 0]]);
+        assert_eq!(features.feature_names(&names(&["x"])), ["1", "x", "x^2"]);
+        assert_eq!(
+            features.transform(&[vec![3.0], vec![-2.0]]).unwrap(),
+            [[1.0, 3.0, 9.0], [1.0, -2.0, 4.0]]
+        );
+        assert_eq!(
+            features.transform_column(&[3.0, -2.0]),
+            [[1.0, 3.0, 9.0], [1.0, -2.0, 4.0]]
+        );
+    }
+
+    #[test]
+    fn transform_multiplies_the_factors() {
+        let features = PolynomialFeatures::new(2);
+        assert_eq!(
+            features.transform(&[vec![2.0, 3.0]]).unwrap(),
+            [[2.0, 3.0, 4.0, 6.0, 9.0]]
+        );
+        assert_eq!(
+            features.transform(&[vec![1.0, 2.0], vec![3.0]]),
+            Err(LinalgError::RaggedRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,420 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::PreprocessingError;
+use crate::preprocessing::{Transformer, check_width, column, n_columns};
+
+/// Centers every feature on its mean and divides by its standard deviation,
+/// so each has mean 0 and variance 1 on the training data.
+///
+/// A constant feature keeps a scale of 1 and is only centered.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardScaler {
+    pub mean: Vec<f64>,
+    /// Population standard deviation of every feature.
+    pub std: Vec<f64>,
+}
+
+impl StandardScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Transformer for StandardScaler {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        let d = n_columns(x)?;
+        let n = x.len() as f64;
+        self.mean = (0..d).map(|j| column(x, j).sum::<f64>() / n).collect();
+        self.std = (0..d)
+            .map(|j| {
+                let mean = self.mean[j];
+                let variance = column(x, j).map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+                nonzero_scale(variance.sqrt())
+            })
+            .collect();
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        affine(x, &self.mean, &self.std, |v, shift, scale| {
+            (v - shift) / scale
+        })
+    }
+
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        affine(x, &self.mean, &self.std, |v, shift, scale| {
+            v * scale + shift
+        })
+    }
+}
+
+/// Maps every feature linearly onto `feature_range`, `[0, 1]` by default,
+/// using its minimum and maximum on the training data.
+///
+/// A constant feature is only shifted to the low end of the range. Values
+/// outside the training range map outside `feature_range`. Fitting fails
+/// with `PreprocessingError::InvalidRange` unless `low < high`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub feature_range: (f64, f64),
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        Self::with_range(0.0, 1.0)
+    }
+
+    pub fn with_range(low: f64, high: f64) -> Self {
+        Self {
+            feature_range: (low, high),
+            min: Vec::new(),
+            max: Vec::new(),
+        }
+    }
+
+    // Per-feature `(shift, scale)` with `x' = (x - shift) / scale`.
+    fn shift_and_scale(&self) -> (Vec<f64>, Vec<f64>) {
+        let (low, high) = self.feature_range;
+        let scale: Vec<f64> = self
+            .min
+            .iter()
+            .zip(&self.max)
+            .map(|(min, max)| nonzero_scale(max - min) / (high - low))
+            .collect();
+        let shift = self
+            .min
+            .iter()
+            .zip(&scale)
+            .map(|(min, scale)| min - low * scale)
+            .collect();
+        (shift, scale)
+    }
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transformer for MinMaxScaler {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        let (low, high) = self.feature_range;
+        if low.partial_cmp(&high) != Some(Ordering::Less) {
+            return Err(PreprocessingError::InvalidRange { low, high });
+        }
+        let d = n_columns(x)?;
+        self.min = (0..d)
+            .map(|j| column(x, j).fold(f64::INFINITY, f64::min))
+            .collect();
+        self.max = (0..d)
+            .map(|j| column(x, j).fold(f64::NEG_INFINITY, f64::max))
+            .collect();
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        let (shift, scale) = self.shift_and_scale();
+        affine(x, &shift, &scale, |v, shift, scale| (v - shift) / scale)
+    }
+
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        let (shift, scale) = self.shift_and_scale();
+        affine(x, &shift, &scale, |v, shift, scale| v * scale + shift)
+    }
+}
+
+/// Centers every feature on its median and divides by its interquartile
+/// range, which unlike `StandardScaler` ignores how far out outliers lie.
+///
+/// `quantile_range` selects the range, `(25.0, 75.0)` by default. Quantiles
+/// interpolate linearly between samples. A feature with a zero range keeps a
+/// scale of 1. Fitting fails with `PreprocessingError::InvalidRange` unless
+/// `0 <= low < high <= 100`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobustScaler {
+    /// Lower and upper percentile, in `[0, 100]`.
+    pub quantile_range: (f64, f64),
+    pub center: Vec<f64>,
+    pub scale: Vec<f64>,
+}
+
+impl RobustScaler {
+    pub fn new() -> Self {
+        Self::with_quantile_range(25.0, 75.0)
+    }
+
+    pub fn with_quantile_range(low: f64, high: f64) -> Self {
+        Self {
+            quantile_range: (low, high),
+            center: Vec::new(),
+            scale: Vec::new(),
+        }
+    }
+}
+
+impl Default for RobustScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transformer for RobustScaler {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        let (low, high) = self.quantile_range;
+        let valid = (0.0..100.0).contains(&low) && low < high && high <= 100.0;
+        if !valid {
+            return Err(PreprocessingError::InvalidRange { low, high });
+        }
+        let d = n_columns(x)?;
+        self.center.clear();
+        self.scale.clear();
+        for j in 0..d {
+            let mut values: Vec<f64> = column(x, j).collect();
+            values.sort_by(f64::total_cmp);
+            self.center.push(quantile(&values, 0.5));
+            let range = quantile(&values, high / 100.0) - quantile(&values, low / 100.0);
+            self.scale.push(nonzero_scale(range));
+        }
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        affine(x, &self.center, &self.scale, |v, shift, scale| {
+            (v - shift) / scale
+        })
+    }
+
+    fn inverse_transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        affine(x, &self.center, &self.scale, |v, shift, scale| {
+            v * scale + shift
+        })
+    }
+}
+
+/// The vector norm `Normalizer` divides by.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Norm {
+    /// Sum of absolute values.
+    L1,
+    /// Euclidean length.
+    #[default]
+    L2,
+    /// Largest absolute value.
+    Max,
+}
+
+/// Scales every sample (row, not feature) to unit norm, for when only the
+/// direction of a sample matters. All-zero rows are left as they are.
+///
+/// It learns nothing from the data, and since the norms are discarded it
+/// has no inverse: `inverse_transform` fails with
+/// `PreprocessingError::NotInvertible`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Normalizer {
+    pub norm: Norm,
+}
+
+impl Normalizer {
+    pub fn new(norm: Norm) -> Self {
+        Self { norm }
+    }
+}
+
+impl Transformer for Normalizer {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), PreprocessingError> {
+        n_columns(x)?;
+        Ok(())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        Ok(x.iter()
+            .map(|row| {
+                let norm = match self.norm {
+                    Norm::L1 => row.iter().map(|v| v.abs()).sum(),
+                    Norm::L2 => row.iter().map(|v| v * v).sum::<f64>().sqrt(),
+                    Norm::Max => row.iter().fold(0.0, |max: f64, v| max.max(v.abs())),
+                };
+                let norm = nonzero_scale(norm);
+                row.iter().map(|v| v / norm).collect()
+            })
+            .collect())
+    }
+
+    fn inverse_transform(&self, _: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+        Err(PreprocessingError::NotInvertible)
+    }
+}
+
+// Applies `f(value, shift[j], scale[j])` to every value in column `j`.
+fn affine(
+    x: &[Vec<f64>],
+    shift: &[f64],
+    scale: &[f64],
+    f: impl Fn(f64, f64, f64) -> f64,
+) -> Result<Vec<Vec<f64>>, PreprocessingError> {
+    check_width(x, shift.len())?;
+    Ok(x.iter()
+        .map(|row| {
+            row.iter()
+                .zip(shift.iter().zip(scale))
+                .map(|(&v, (&shift, &scale))| f(v, shift, scale))
+                .collect()
+        })
+        .collect())
+}
+
+// A zero scale would divide by zero; leave such features unscaled.
+fn nonzero_scale(scale: f64) -> f64 {
+    if scale == 0.0 { 1.0 } else { scale }
+}
+
+// The `q`-quantile of sorted, non-empty `values`, interpolating linearly.
+fn quantile(values: &[f64], q: f64) -> f64 {
+    let position = q.clamp(0.0, 1.0) * (values.len() - 1) as f64;
+    let (below, fraction) = (position.floor() as usize, position.fract());
+    match values.get(below + 1) {
+        Some(above) => values[below] + fraction * (above - values[below]),
+        None => values[below],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LinalgError;
+
+    fn data() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, -4.0, 7.0],
+            vec![2.0, 0.5, 7.0],
+            vec![4.0, 3.0, 7.0],
+            vec![9.0, -1.0, 7.0],
+        ]
+    }
+
+    fn assert_close(a: &[Vec<f64>], b: &[Vec<f64>]) {
+        for (row_a, row_b) in a.iter().zip(b) {
+            for (x, y) in row_a.iter().zip(row_b) {
+                assert!((x - y).abs() < 1e-12, "{} != {}", x, y);
+            }
+        }
+    }
+
+    fn assert_round_trip(mut scaler: impl Transformer) {
+        let x = data();
+        let scaled = scaler.fit_transform(&x).unwrap();
+        assert_close(&scaler.inverse_transform(&scaled).unwrap(), &x);
+    }
+
+    #[test]
+    fn inverse_transform_undoes_transform() {
+        assert_round_trip(StandardScaler::new());
+        assert_round_trip(MinMaxScaler::new());
+        assert_round_trip(MinMaxScaler::with_range(-1.0, 1.0));
+        assert_round_trip(RobustScaler::new());
+        assert_round_trip(RobustScaler::with_quantile_range(10.0, 90.0));
+    }
+
+    #[test]
+    fn standard_scaler_gives_zero_mean_and_unit_variance() {
+        let scaled = StandardScaler::new().fit_transform(&data()).unwrap();
+        for j in 0..2 {
+            let mean = column(&scaled, j).sum::<f64>() / 4.0;
+            let variance = column(&scaled, j).map(|v| v * v).sum::<f64>() / 4.0;
+            assert!(mean.abs() < 1e-12);
+            assert!((variance - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn min_max_scaler_maps_onto_the_range() {
+        let scaled = MinMaxScaler::with_range(-1.0, 1.0)
+            .fit_transform(&data())
+            .unwrap();
+        assert_eq!(
+            column(&scaled, 0).collect::<Vec<_>>(),
+            [-1.0, -0.75, -0.25, 1.0]
+        );
+        assert_eq!(column(&scaled, 1).fold(f64::INFINITY, f64::min), -1.0);
+        assert_eq!(column(&scaled, 1).fold(f64::NEG_INFINITY, f64::max), 1.0);
+    }
+
+    #[test]
+    fn constant_columns_are_only_shifted() {
+        let x = data();
+        let standard = StandardScaler::new().fit_transform(&x).unwrap();
+        assert!(column(&standard, 2).all(|v| v == 0.0));
+
+        let mut min_max = MinMaxScaler::with_range(2.0, 3.0);
+        let scaled = min_max.fit_transform(&x).unwrap();
+        assert!(column(&scaled, 2).all(|v| v == 2.0));
+        assert_close(&min_max.inverse_transform(&scaled).unwrap(), &x);
+
+        let robust = RobustScaler::new().fit_transform(&x).unwrap();
+        assert!(column(&robust, 2).all(|v| v == 0.0));
+    }
+
+    #[test]
+    fn min_max_scaler_rejects_an_empty_range() {
+        for (low, high) in [(1.0, 1.0), (2.0, 1.0), (f64::NAN, 1.0)] {
+            let err = MinMaxScaler::with_range(low, high)
+                .fit(&data())
+                .unwrap_err();
+            assert!(matches!(err, PreprocessingError::InvalidRange { .. }));
+        }
+    }
+
+    #[test]
+    fn robust_scaler_uses_median_and_interquartile_range() {
+        let x = vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![100.0]];
+        let mut scaler = RobustScaler::new();
+        scaler.fit(&x).unwrap();
+        assert_eq!((scaler.center[0], scaler.scale[0]), (3.0, 2.0));
+    }
+
+    #[test]
+    fn robust_scaler_rejects_an_invalid_quantile_range() {
+        for (low, high) in [(90.0, 10.0), (-5.0, 150.0), (f64::NAN, 75.0)] {
+            let err = RobustScaler::with_quantile_range(low, high)
+                .fit(&data())
+                .unwrap_err();
+            assert!(matches!(err, PreprocessingError::InvalidRange { .. }));
+        }
+        let mut full = RobustScaler::with_quantile_range(0.0, 100.0);
+        assert!(full.fit(&data()).is_ok());
+    }
+
+    #[test]
+    fn normalizer_scales_rows_and_has_no_inverse() {
+        let x = vec![vec![3.0, -4.0], vec![0.0, 0.0]];
+        let l2 = Normalizer::new(Norm::L2).transform(&x).unwrap();
+        assert_eq!(l2, [[0.6, -0.8], [0.0, 0.0]]);
+        let l1 = Normalizer::new(Norm::L1).transform(&x).unwrap();
+        assert_close(&l1, &[vec![3.0 / 7.0, -4.0 / 7.0], vec![0.0, 0.0]]);
+        let max = Normalizer::new(Norm::Max).transform(&x).unwrap();
+        assert_eq!(max[0], [0.75, -1.0]);
+        assert_eq!(
+            Normalizer::default().inverse_transform(&x),
+            Err(PreprocessingError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn transform_checks_the_number_of_features() {
+        let mut scaler = StandardScaler::new();
+        scaler.fit(&data()).unwrap();
+        assert_eq!(
+            scaler.transform(&[vec![1.0, 2.0]]),
+            Err(PreprocessingError::Linalg(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }))
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Seeding for the crate's random components.
+//!
+//! Everything random (weight initialization, synthetic data, shuffling and
+//! splitting) takes either an `Rng` or a seed, so any run can be repeated
+//! exactly. Where the seed is optional, leaving it out draws one from the OS.
+
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A generator seeded with `seed`, or from OS entropy when there is none.
+pub fn rng_from_seed(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// A draw from the standard normal distribution, by the Box–Muller
+/// transform.
+pub fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // 1 - u lies in (0, 1], so the logarithm is finite
+    let u1 = 1.0 - rng.r#gen::<f64>();
+    let u2 = rng.r#gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// A draw from the uniform distribution on `[low, high)`. With `high < low`
+/// it falls in `(high, low]` instead, and it is `low` when they are equal.
+pub fn uniform<R: Rng + ?Sized>(rng: &mut R, low: f64, high: f64) -> f64 {
+    low + (high - low) * rng.r#gen::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Fit, LinRegModel, MultiLinRegModel, SoftmaxRegression, Solver};
+    use crate::training::{GradientDescent, TrainConfig};
+
+    #[test]
+    fn a_seed_repeats_the_same_draws() {
+        let draws = |seed| {
+            let mut rng = rng_from_seed(Some(seed));
+            (0..5).map(|_| rng.r#gen::<f64>()).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(42), draws(42));
+        assert_ne!(draws(42), draws(43));
+    }
+
+    #[test]
+    fn seeded_initialization_is_repeatable() {
+        let linear = || LinRegModel::with_rng(&mut rng_from_seed(Some(3)));
+        assert_eq!(linear(), linear());
+
+        let multi = || MultiLinRegModel::with_rng(4, &mut rng_from_seed(Some(3)));
+        assert_eq!(multi(), multi());
+
+        let softmax = || SoftmaxRegression::with_rng(4, 3, &mut rng_from_seed(Some(3)));
+        assert_eq!(softmax(), softmax());
+    }
+
+    #[test]
+    fn seeded_shuffled_training_is_repeatable() {
+        let x: Vec<Vec<f64>> = (0..20).map(|i| vec![i as f64 / 10.0]).collect();
+        let y: Vec<f64> = x.iter().map(|row| 2.0 * row[0] + 1.0).collect();
+        let config = TrainConfig::new(30).batch_size(4).seed(9);
+        let solver = Solver::GradientDescent(GradientDescent::new(0.05, 30).config(config));
+        let fit = || MultiLinRegModel::fit(&x, &y, &solver).unwrap();
+        assert_eq!(fit(), fit());
+    }
+
+    #[test]
+    fn uniform_draws_between_the_bounds() {
+        let mut rng = rng_from_seed(Some(0));
+        for _ in 0..1000 {
+            assert!((-2.0..5.0).contains(&uniform(&mut rng, -2.0, 5.0)));
+            let reversed = uniform(&mut rng, 5.0, -2.0);
+            assert!(reversed > -2.0 && reversed <= 5.0);
+        }
+        assert_eq!(uniform(&mut rng, 1.5, 1.5), 1.5);
+    }
+}
@@ -0,0 +1,318 @@
+//! Penalties on model weights that trade training fit for smaller weights.
+//!
+//! Every penalty leaves the bias alone. With strength `α` and L1 ratio `ρ`
+//! the penalized objective is
+//!
+//! ```text
+//! loss + α · (ρ·‖w‖₁ + (1 - ρ)·‖w‖²)
+//! ```
+//!
+//! so ridge is `ρ = 0`, lasso is `ρ = 1` and elastic net is anything in
+//! between. Gradient descent handles the smooth L2 part through the
+//! gradient. With plain SGD the L1 part is a proximal (soft-thresholding)
+//! step after each update, which lets lasso drive weights to exactly zero;
+//! optimizers that rescale their steps get its subgradient instead, since
+//! the proximal step assumes every weight moved by `lr · g`.
+
+use crate::error::ModelError;
+
+/// Regularization applied to a model's weights.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Penalty {
+    #[default]
+    None,
+    /// L2 penalty `α·‖w‖²`.
+    Ridge(f64),
+    /// L1 penalty `α·‖w‖₁`.
+    Lasso(f64),
+    /// Mix of both, `α·(ρ·‖w‖₁ + (1 - ρ)·‖w‖²)` with `ρ = l1_ratio`.
+    ElasticNet { alpha: f64, l1_ratio: f64 },
+}
+
+impl Penalty {
+    /// Short identifier, e.g. `"lasso"`, recorded alongside trained models.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Penalty::None => "none",
+            Penalty::Ridge(_) => "ridge",
+            Penalty::Lasso(_) => "lasso",
+            Penalty::ElasticNet { .. } => "elastic_net",
+        }
+    }
+
+    /// Coefficient of `‖w‖₁`.
+    pub fn l1_strength(&self) -> f64 {
+        match *self {
+            Penalty::None | Penalty::Ridge(_) => 0.0,
+            Penalty::Lasso(alpha) => alpha,
+            Penalty::ElasticNet { alpha, l1_ratio } => alpha * l1_ratio,
+        }
+    }
+
+    /// Coefficient of `‖w‖²`.
+    pub fn l2_strength(&self) -> f64 {
+        match *self {
+            Penalty::None | Penalty::Lasso(_) => 0.0,
+            Penalty::Ridge(alpha) => alpha,
+            Penalty::ElasticNet { alpha, l1_ratio } => alpha * (1.0 - l1_ratio),
+        }
+    }
+
+    /// Checks that the strength is a non-negative number and that an
+    /// elastic-net `l1_ratio` is within `[0, 1]`. Every fit calls this first.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        let (alpha, l1_ratio) = match *self {
+            Penalty::None => return Ok(()),
+            Penalty::Ridge(alpha) | Penalty::Lasso(alpha) => (alpha, 0.0),
+            Penalty::ElasticNet { alpha, l1_ratio } => (alpha, l1_ratio),
+        };
+        if !alpha.is_finite() || alpha < 0.0 {
+            return Err(ModelError::InvalidAlpha(alpha));
+        }
+        if !(0.0..=1.0).contains(&l1_ratio) {
+            return Err(ModelError::InvalidL1Ratio(l1_ratio));
+        }
+        Ok(())
+    }
+
+    /// Whether the penalty has an L1 part, which rules out a closed-form fit.
+    pub fn has_l1(&self) -> bool {
+        self.l1_strength() != 0.0
+    }
+
+    /// Value of the penalty term for weights `w`.
+    pub fn value(&self, w: &[f64]) -> f64 {
+        // Skipping a zero-strength part keeps `0 · ∞` from turning a
+        // diverged loss into NaN
+        let mut value = 0.0;
+        if self.has_l1() {
+            value += self.l1_strength() * w.iter().map(|w_j| w_j.abs()).sum::<f64>();
+        }
+        if self.l2_strength() != 0.0 {
+            value += self.l2_strength() * w.iter().map(|w_j| w_j * w_j).sum::<f64>();
+        }
+        value
+    }
+
+    /// Adds the gradient of the smooth (L2) part to `grads`.
+    pub fn add_gradient(&self, w: &[f64], grads: &mut [f64]) {
+        let l2 = self.l2_strength();
+        if l2 == 0.0 {
+            return;
+        }
+        for (g, w_j) in grads.iter_mut().zip(w) {
+            *g += 2.0 * l2 * w_j;
+        }
+    }
+
+    /// Adds a subgradient of the L1 part, `l1_strength · sign(w_j)` and `0`
+    /// at `w_j = 0`, to `grads`.
+    pub fn add_l1_subgradient(&self, w: &[f64], grads: &mut [f64]) {
+        let l1 = self.l1_strength();
+        if l1 == 0.0 {
+            return;
+        }
+        for (g, w_j) in grads.iter_mut().zip(w) {
+            if *w_j != 0.0 {
+                *g += l1 * w_j.signum();
+            }
+        }
+    }
+
+    /// Proximal step for the L1 part after a gradient step of size
+    /// `step_size`: shrinks every weight towards zero by
+    /// `step_size · l1_strength`, clamping at zero.
+    pub fn shrink(&self, w: &mut [f64], step_size: f64) {
+        let threshold = step_size * self.l1_strength();
+        if threshold == 0.0 {
+            return;
+        }
+        for w_j in w.iter_mut() {
+            *w_j = soft_threshold(*w_j, threshold);
+        }
+    }
+}
+
+/// `sign(x) · max(|x| - threshold, 0)`.
+pub fn soft_threshold(x: f64, threshold: f64) -> f64 {
+    if x > threshold {
+        x - threshold
+    } else if x < -threshold {
+        x + threshold
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Fit, LogisticRegression, MultiLinRegModel, Solver};
+    use crate::optim::{Adam, Sgd};
+    use crate::training::TrainConfig;
+
+    // y = 2·x0 + 0.05·x1 + 1, where x0 and x1 are centered and orthogonal
+    // with Σx0² = 20 and Σx1² = 8, so each weight can be worked out alone
+    fn data() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x0 = [-2.0, -1.0, 0.0, 1.0, 2.0, -2.0, -1.0, 0.0, 1.0, 2.0];
+        let x1 = [1.0, -1.0, 0.0, -1.0, 1.0, -1.0, 1.0, 0.0, 1.0, -1.0];
+        let x: Vec<Vec<f64>> = x0.iter().zip(&x1).map(|(&a, &b)| vec![a, b]).collect();
+        let y = x
+            .iter()
+            .map(|row| 2.0 * row[0] + 0.05 * row[1] + 1.0)
+            .collect();
+        (x, y)
+    }
+
+    fn assert_close(found: &[f64], expected: &[f64], tolerance: f64) {
+        assert_eq!(found.len(), expected.len());
+        for (f, e) in found.iter().zip(expected) {
+            assert!((f - e).abs() < tolerance, "{:?} != {:?}", found, expected);
+        }
+    }
+
+    #[test]
+    fn invalid_penalties_are_rejected() {
+        for penalty in [
+            Penalty::None,
+            Penalty::Ridge(0.0),
+            Penalty::Lasso(2.0),
+            Penalty::ElasticNet {
+                alpha: 1.0,
+                l1_ratio: 1.0,
+            },
+        ] {
+            assert_eq!(penalty.validate(), Ok(()));
+        }
+
+        assert_eq!(
+            Penalty::Ridge(-1.0).validate(),
+            Err(ModelError::InvalidAlpha(-1.0))
+        );
+        assert!(matches!(
+            Penalty::Lasso(f64::NAN).validate(),
+            Err(ModelError::InvalidAlpha(_))
+        ));
+        for l1_ratio in [-0.1, 1.5] {
+            let penalty = Penalty::ElasticNet {
+                alpha: 1.0,
+                l1_ratio,
+            };
+            assert_eq!(
+                penalty.validate(),
+                Err(ModelError::InvalidL1Ratio(l1_ratio))
+            );
+        }
+    }
+
+    #[test]
+    fn fitting_validates_the_penalty() {
+        let (x, y) = data();
+        let negative = Penalty::Lasso(-0.1);
+        let error = ModelError::InvalidAlpha(-0.1);
+
+        let exact = |penalty| MultiLinRegModel::fit(&x, &y, &Solver::exact(penalty));
+        assert_eq!(exact(negative).unwrap_err(), error);
+        assert_eq!(exact(Penalty::Ridge(-0.1)).unwrap_err(), error);
+
+        let config = TrainConfig::new(10).penalty(negative);
+        let mut sgd = Sgd::new(0.01);
+        let result = MultiLinRegModel::new(2).train_with(&x, &y, &mut sgd, &config);
+        assert_eq!(result.unwrap_err(), error);
+        let result = LogisticRegression::new(2).train_with(&x, &[0; 10], &mut sgd, &config);
+        assert_eq!(result.unwrap_err(), error);
+    }
+
+    #[test]
+    fn value_and_gradients() {
+        let penalty = Penalty::ElasticNet {
+            alpha: 2.0,
+            l1_ratio: 0.25,
+        };
+        // 2 · (0.25 · 3 + 0.75 · 5)
+        assert_eq!(penalty.value(&[1.0, -2.0]), 9.0);
+        assert_eq!(Penalty::None.value(&[f64::INFINITY]), 0.0);
+
+        let w = [2.0, -1.0, 0.0];
+        let mut grads = [0.0; 3];
+        Penalty::Ridge(0.5).add_gradient(&w, &mut grads);
+        assert_eq!(grads, [2.0, -1.0, 0.0]);
+
+        let mut grads = [0.0; 3];
+        Penalty::Lasso(0.5).add_l1_subgradient(&w, &mut grads);
+        assert_eq!(grads, [0.5, -0.5, 0.0]);
+        Penalty::Ridge(0.5).add_l1_subgradient(&w, &mut grads);
+        assert_eq!(grads, [0.5, -0.5, 0.0]);
+    }
+
+    #[test]
+    fn proximal_step_soft_thresholds() {
+        assert_eq!(soft_threshold(3.0, 1.0), 2.0);
+        assert_eq!(soft_threshold(-3.0, 1.0), -2.0);
+        assert_eq!(soft_threshold(0.5, 1.0), 0.0);
+
+        let mut w = [0.3, -2.0, 1.0];
+        Penalty::Lasso(1.0).shrink(&mut w, 0.5);
+        assert_eq!(w, [0.0, -1.5, 0.5]);
+        Penalty::Ridge(1.0).shrink(&mut w, 0.5);
+        assert_eq!(w, [0.0, -1.5, 0.5]);
+    }
+
+    #[test]
+    fn ridge_closed_form_shrinks_the_weights() {
+        let (x, y) = data();
+        let ols = MultiLinRegModel::fit_closed_form(&x, &y).unwrap();
+        assert_close(&ols.w, &[2.0, 0.05], 1e-12);
+
+        // With orthogonal features w_j = Σx_j·y / (Σx_j² + nα)
+        let ridge = MultiLinRegModel::fit_ridge(&x, &y, 0.5).unwrap();
+        assert_close(&ridge.w, &[40.0 / 25.0, 0.4 / 13.0], 1e-12);
+        assert!((ridge.b - 1.0).abs() < 1e-12);
+        assert!(ridge.w.iter().zip(&ols.w).all(|(r, o)| r.abs() < o.abs()));
+
+        let mut trained = MultiLinRegModel {
+            w: vec![0.0; 2],
+            b: 0.0,
+        };
+        let config = TrainConfig::new(5000).penalty(Penalty::Ridge(0.5));
+        trained
+            .train_with(&x, &y, &mut Sgd::new(0.05), &config)
+            .unwrap();
+        assert_close(&trained.w, &ridge.w, 1e-9);
+    }
+
+    #[test]
+    fn lasso_zeroes_an_irrelevant_coefficient() {
+        // The correlation of x1 with y, 2/n · Σx1² · 0.05 = 0.08, is below
+        // α = 0.1, so lasso sets its weight to exactly zero and shrinks w0
+        // to (8 - 0.1) / 4
+        let (x, y) = data();
+        let penalty = Penalty::Lasso(0.1);
+        let exact = MultiLinRegModel::fit_coordinate_descent(&x, &y, penalty, 1000, 1e-12).unwrap();
+        assert_eq!(exact.w[1], 0.0);
+        assert_close(&exact.w, &[1.975, 0.0], 1e-12);
+        assert!((exact.b - 1.0).abs() < 1e-12);
+
+        // SGD takes a proximal step, which also lands exactly on zero
+        let config = TrainConfig::new(2000).penalty(penalty);
+        let mut proximal = MultiLinRegModel {
+            w: vec![0.5, 0.5],
+            b: 0.0,
+        };
+        proximal
+            .train_with(&x, &y, &mut Sgd::new(0.05), &config)
+            .unwrap();
+        assert_eq!(proximal.w[1], 0.0);
+        assert_close(&proximal.w, &exact.w, 1e-9);
+
+        // Adam gets the subgradient instead, which only hovers near zero
+        let mut subgradient = MultiLinRegModel {
+            w: vec![0.5, 0.5],
+            b: 0.0,
+        };
+        subgradient
+            .train_with(&x, &y, &mut Adam::new(0.01), &config)
+            .unwrap();
+        assert_close(&subgradient.w, &exact.w, 1e-2);
+    }
+}
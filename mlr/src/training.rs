@@ -0,0 +1,513 @@
+//! The gradient-descent loop shared by every iteratively trained model.
+
+use std::fmt;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+
+use crate::optim::{Optimizer, OptimizerKind};
+use crate::random::rng_from_seed;
+use crate::regularization::Penalty;
+
+/// Which samples each optimizer step computes its gradient on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Batching {
+    /// One step per epoch on the whole training set.
+    Full,
+    /// `ceil(n / size)` steps per epoch on consecutive chunks of `size`
+    /// samples, reshuffled every epoch if `shuffle` is set.
+    MiniBatch { size: usize, shuffle: bool },
+}
+
+impl Batching {
+    /// Mini-batches of a single shuffled sample.
+    pub fn stochastic() -> Self {
+        Batching::MiniBatch {
+            size: 1,
+            shuffle: true,
+        }
+    }
+}
+
+/// Settings for one gradient-descent training run.
+///
+/// Training runs for `epochs` epochs unless one of the optional stopping
+/// rules fires first; every rule is checked once at the end of each epoch.
+#[derive(Debug, Clone)]
+pub struct TrainConfig {
+    pub epochs: usize,
+    pub batching: Batching,
+    /// Regularization on the model's weights. Training losses in the report
+    /// and history include it; validation losses don't.
+    pub penalty: Penalty,
+    /// Stop once the training loss changes by less than this between epochs.
+    pub loss_tolerance: Option<f64>,
+    /// Stop once the L2 norm of the epoch's mean gradient is below this.
+    pub grad_tolerance: Option<f64>,
+    /// Stop after this many epochs without improving the validation loss.
+    /// Training then ends on the parameters with the best validation loss.
+    pub patience: Option<usize>,
+    /// Share of the samples held out to compute the validation loss. Only
+    /// used when `patience` is set.
+    pub validation_fraction: f64,
+    /// Stop once training has run for this long.
+    pub max_duration: Option<Duration>,
+    /// Keep a `TrainingHistory` in the report.
+    pub record_history: bool,
+    /// Seed for the validation split and mini-batch shuffling, and for the
+    /// initial weights of models fitted through `models::Fit`. Unset, every
+    /// run differs.
+    pub seed: Option<u64>,
+}
+
+impl TrainConfig {
+    pub fn new(epochs: usize) -> Self {
+        Self {
+            epochs,
+            batching: Batching::Full,
+            penalty: Penalty::None,
+            loss_tolerance: None,
+            grad_tolerance: None,
+            patience: None,
+            validation_fraction: 0.1,
+            max_duration: None,
+            record_history: false,
+            seed: None,
+        }
+    }
+
+    pub fn batching(mut self, batching: Batching) -> Self {
+        self.batching = batching;
+        self
+    }
+
+    pub fn batch_size(self, size: usize) -> Self {
+        self.batching(Batching::MiniBatch {
+            size,
+            shuffle: true,
+        })
+    }
+
+    pub fn penalty(mut self, penalty: Penalty) -> Self {
+        self.penalty = penalty;
+        self
+    }
+
+    pub fn loss_tolerance(mut self, tolerance: f64) -> Self {
+        self.loss_tolerance = Some(tolerance);
+        self
+    }
+
+    pub fn grad_tolerance(mut self, tolerance: f64) -> Self {
+        self.grad_tolerance = Some(tolerance);
+        self
+    }
+
+    pub fn patience(mut self, epochs: usize) -> Self {
+        self.patience = Some(epochs);
+        self
+    }
+
+    pub fn validation_fraction(mut self, fraction: f64) -> Self {
+        self.validation_fraction = fraction;
+        self
+    }
+
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    pub fn record_history(mut self) -> Self {
+        self.record_history = true;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// Why a training run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// All `epochs` epochs ran.
+    MaxEpochs,
+    /// The training loss stopped changing by more than `loss_tolerance`.
+    LossTolerance,
+    /// The gradient norm fell below `grad_tolerance`.
+    GradTolerance,
+    /// The validation loss didn't improve for `patience` epochs.
+    Patience,
+    /// `max_duration` elapsed.
+    Timeout,
+    /// The training loss became NaN or infinite, usually because the
+    /// learning rate is too large.
+    Diverged,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            StopReason::MaxEpochs => "reached the maximum number of epochs",
+            StopReason::LossTolerance => "loss stopped improving",
+            StopReason::GradTolerance => "gradient norm fell below the tolerance",
+            StopReason::Patience => "validation loss stopped improving",
+            StopReason::Timeout => "ran out of time",
+            StopReason::Diverged => "loss diverged",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+/// Summary of a finished training run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingReport {
+    pub epochs_run: usize,
+    pub stop_reason: StopReason,
+    /// Training loss at the final parameters (NaN if there were no samples).
+    pub final_loss: f64,
+    /// Lowest validation loss seen, when `patience` was set.
+    pub best_validation_loss: Option<f64>,
+    pub elapsed: Duration,
+    /// Per-epoch record, when `record_history` was set.
+    pub history: Option<TrainingHistory>,
+}
+
+/// What happened in each epoch of a training run, for diagnosing it after
+/// the fact (see `visualization::plot_loss_curve`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrainingHistory {
+    /// Training loss of each epoch, averaged over its batches.
+    pub loss: Vec<f64>,
+    /// Validation loss after each epoch; empty unless `patience` was set.
+    pub validation_loss: Vec<f64>,
+    /// L2 norm of each epoch's mean gradient.
+    pub grad_norm: Vec<f64>,
+    /// Parameters at the end of each epoch, in the order the model packs
+    /// them (see the model's `train_with`).
+    pub params: Vec<Vec<f64>>,
+}
+
+impl TrainingHistory {
+    /// Number of epochs recorded.
+    pub fn len(&self) -> usize {
+        self.loss.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.loss.is_empty()
+    }
+}
+
+/// Everything needed to train a model by gradient descent, so a model can be
+/// refitted from scratch (see `models::Fit`).
+#[derive(Debug, Clone)]
+pub struct GradientDescent {
+    pub optimizer: OptimizerKind,
+    pub learning_rate: f64,
+    pub config: TrainConfig,
+}
+
+impl GradientDescent {
+    /// Plain SGD for `epochs` full-batch epochs.
+    pub fn new(learning_rate: f64, epochs: usize) -> Self {
+        Self {
+            optimizer: OptimizerKind::Sgd,
+            learning_rate,
+            config: TrainConfig::new(epochs),
+        }
+    }
+
+    pub fn optimizer(mut self, optimizer: OptimizerKind) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+
+    pub fn config(mut self, config: TrainConfig) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+/// Runs optimizer steps over `n_samples` samples until `config` says to stop.
+///
+/// `loss_and_gradient(params, batch)` returns the loss averaged over the
+/// sample indices in `batch` and its gradient, with one entry per parameter.
+/// The epoch loss and gradient checked by the stopping rules are the means
+/// over the epoch's batches, weighted by batch size.
+///
+/// `config.penalty` applies to `params[weights]`, leaving biases unpenalized.
+/// Its L1 part is a proximal step after every update when the optimizer
+/// takes plain steps, and part of the gradient otherwise.
+pub(crate) fn gradient_descent<F>(
+    params: &mut [f64],
+    weights: Range<usize>,
+    n_samples: usize,
+    optimizer: &mut dyn Optimizer,
+    config: &TrainConfig,
+    mut loss_and_gradient: F,
+) -> TrainingReport
+where
+    F: FnMut(&[f64], &[usize]) -> (f64, Vec<f64>),
+{
+    let penalty = config.penalty;
+    let proximal = optimizer.plain_steps();
+    let objective = |loss_and_gradient: &mut F, params: &[f64], batch: &[usize]| {
+        let (loss, mut grads) = loss_and_gradient(params, batch);
+        let w = &params[weights.clone()];
+        penalty.add_gradient(w, &mut grads[weights.clone()]);
+        if !proximal {
+            penalty.add_l1_subgradient(w, &mut grads[weights.clone()]);
+        }
+        (loss + penalty.value(w), grads)
+    };
+
+    let start = Instant::now();
+    let mut report = TrainingReport {
+        epochs_run: 0,
+        stop_reason: StopReason::MaxEpochs,
+        final_loss: f64::NAN,
+        best_validation_loss: None,
+        elapsed: Duration::ZERO,
+        history: None,
+    };
+    if n_samples == 0 {
+        return report; // Avoid training on empty data
+    }
+
+    let mut rng = rng_from_seed(config.seed);
+    let mut order: Vec<usize> = (0..n_samples).collect();
+
+    // Hold out a random validation set, keeping at least one training sample
+    let mut validation = Vec::new();
+    if config.patience.is_some() {
+        let n_validation = (n_samples as f64 * config.validation_fraction).ceil() as usize;
+        order.shuffle(&mut rng);
+        validation = order.split_off(n_samples - n_validation.min(n_samples - 1));
+    }
+
+    let (batch_size, shuffle) = match config.batching {
+        Batching::Full => (order.len(), false),
+        Batching::MiniBatch { size, shuffle } => (size.clamp(1, order.len()), shuffle),
+    };
+
+    let mut previous_loss: Option<f64> = None;
+    let mut best: Option<(f64, Vec<f64>)> = None;
+    let mut epochs_since_best = 0;
+    let mut history = config.record_history.then(TrainingHistory::default);
+
+    for epoch in 1..=config.epochs {
+        if shuffle {
+            order.shuffle(&mut rng);
+        }
+
+        let mut epoch_loss = 0.0;
+        let mut epoch_grads = vec![0.0; params.len()];
+        for batch in order.chunks(batch_size) {
+            let (loss, grads) = objective(&mut loss_and_gradient, params, batch);
+            let weight = batch.len() as f64 / order.len() as f64;
+            epoch_loss += weight * loss;
+            for (total, g) in epoch_grads.iter_mut().zip(&grads) {
+                *total += weight * g;
+            }
+            optimizer.step(params, &grads);
+            if proximal {
+                penalty.shrink(&mut params[weights.clone()], optimizer.learning_rate());
+            }
+        }
+        report.epochs_run = epoch;
+
+        let grad_norm = epoch_grads.iter().map(|g| g * g).sum::<f64>().sqrt();
+        let validation_loss = (config.patience.is_some() && !validation.is_empty())
+            .then(|| loss_and_gradient(params, &validation).0);
+
+        if let Some(history) = &mut history {
+            history.loss.push(epoch_loss);
+            history.grad_norm.push(grad_norm);
+            history.validation_loss.extend(validation_loss);
+            history.params.push(params.to_vec());
+        }
+
+        if !epoch_loss.is_finite() {
+            report.stop_reason = StopReason::Diverged;
+            break;
+        }
+
+        if let (Some(patience), Some(validation_loss)) = (config.patience, validation_loss) {
+            if best
+                .as_ref()
+                .is_none_or(|(loss, _)| validation_loss < *loss)
+            {
+                best = Some((validation_loss, params.to_vec()));
+                epochs_since_best = 0;
+            } else {
+                epochs_since_best += 1;
+                if epochs_since_best >= patience {
+                    report.stop_reason = StopReason::Patience;
+                    break;
+                }
+            }
+        }
+
+        if let (Some(tolerance), Some(previous)) = (config.loss_tolerance, previous_loss)
+            && (previous - epoch_loss).abs() < tolerance
+        {
+            report.stop_reason = StopReason::LossTolerance;
+            break;
+        }
+        previous_loss = Some(epoch_loss);
+
+        if config
+            .grad_tolerance
+            .is_some_and(|tolerance| grad_norm < tolerance)
+        {
+            report.stop_reason = StopReason::GradTolerance;
+            break;
+        }
+
+        if config
+            .max_duration
+            .is_some_and(|limit| start.elapsed() >= limit)
+        {
+            report.stop_reason = StopReason::Timeout;
+            break;
+        }
+    }
+
+    if let Some((loss, best_params)) = best {
+        params.copy_from_slice(&best_params);
+        report.best_validation_loss = Some(loss);
+    }
+    report.final_loss = objective(&mut loss_and_gradient, params, &order).0;
+    report.elapsed = start.elapsed();
+    report.history = history;
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optim::Sgd;
+
+    // Fits one parameter `p` to `targets` by the mean of `(p - t)²`, so SGD
+    // with learning rate 0.25 halves the distance to the mean every step.
+    fn train(
+        config: &TrainConfig,
+        learning_rate: f64,
+        start: f64,
+        targets: &[f64],
+    ) -> (f64, TrainingReport) {
+        let mut params = [start];
+        let report = gradient_descent(
+            &mut params,
+            0..1,
+            targets.len(),
+            &mut Sgd::new(learning_rate),
+            config,
+            |params, batch| {
+                let n = batch.len() as f64;
+                let errors = batch.iter().map(|&i| params[0] - targets[i]);
+                let loss = errors.clone().map(|e| e * e).sum::<f64>() / n;
+                (loss, vec![2.0 * errors.sum::<f64>() / n])
+            },
+        );
+        (params[0], report)
+    }
+
+    #[test]
+    fn history_records_every_epoch() {
+        let config = TrainConfig::new(3).record_history();
+        let (p, report) = train(&config, 0.25, 1.0, &[3.0]);
+        assert_eq!(report.stop_reason, StopReason::MaxEpochs);
+        assert_eq!(report.epochs_run, 3);
+        assert_eq!(p, 2.75);
+        assert_eq!(report.final_loss, 0.0625);
+
+        let history = report.history.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.loss, [4.0, 1.0, 0.25]);
+        assert_eq!(history.grad_norm, [4.0, 2.0, 1.0]);
+        assert_eq!(history.params, [[2.0], [2.5], [2.75]]);
+        assert!(history.validation_loss.is_empty());
+
+        let (_, report) = train(&TrainConfig::new(3), 0.25, 1.0, &[3.0]);
+        assert_eq!(report.history, None);
+    }
+
+    #[test]
+    fn loss_tolerance_stops_once_the_loss_settles() {
+        // Losses 4, 1, 1/4, 1/16, 1/64: the first change below 0.1 is the
+        // fifth epoch's
+        let config = TrainConfig::new(100).loss_tolerance(0.1);
+        let (_, report) = train(&config, 0.25, 1.0, &[3.0]);
+        assert_eq!(report.stop_reason, StopReason::LossTolerance);
+        assert_eq!(report.epochs_run, 5);
+    }
+
+    #[test]
+    fn grad_tolerance_stops_on_a_small_gradient() {
+        // Gradient norms 4, 2, 1, 1/2
+        let config = TrainConfig::new(100).grad_tolerance(0.6);
+        let (_, report) = train(&config, 0.25, 1.0, &[3.0]);
+        assert_eq!(report.stop_reason, StopReason::GradTolerance);
+        assert_eq!(report.epochs_run, 4);
+    }
+
+    #[test]
+    fn patience_restores_the_best_parameters() {
+        // Starting halfway between the two targets, training on one moves
+        // away from the other, so the first epoch has the best validation
+        // loss whichever sample is held out
+        let config = TrainConfig::new(100)
+            .patience(2)
+            .validation_fraction(0.5)
+            .record_history()
+            .seed(7);
+        let (p, report) = train(&config, 0.25, 1.5, &[0.0, 3.0]);
+        assert_eq!(report.stop_reason, StopReason::Patience);
+        assert_eq!(report.epochs_run, 3);
+        assert_eq!(report.best_validation_loss, Some(5.0625));
+
+        let history = report.history.unwrap();
+        assert_eq!(history.validation_loss.len(), 3);
+        assert!(history.validation_loss.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!([p], history.params[0].as_slice());
+        assert_eq!((p - 1.5).abs(), 0.75);
+        assert_ne!(history.params[2], history.params[0]);
+    }
+
+    #[test]
+    fn max_duration_stops_after_the_epoch_that_used_it_up() {
+        let config = TrainConfig::new(100).max_duration(Duration::ZERO);
+        let (p, report) = train(&config, 0.25, 1.0, &[3.0]);
+        assert_eq!(report.stop_reason, StopReason::Timeout);
+        assert_eq!(report.epochs_run, 1);
+        assert_eq!(p, 2.0);
+    }
+
+    #[test]
+    fn divergence_stops_on_a_non_finite_loss() {
+        // Each step multiplies the distance to the target by about -4e100:
+        // the losses are 4, ~1.6e201 and then infinite
+        let config = TrainConfig::new(100).record_history();
+        let (_, report) = train(&config, 1e100, 1.0, &[3.0]);
+        assert_eq!(report.stop_reason, StopReason::Diverged);
+        assert_eq!(report.epochs_run, 3);
+        assert!(!report.final_loss.is_finite());
+        let history = report.history.unwrap();
+        assert!(history.loss[..2].iter().all(|loss| loss.is_finite()));
+        assert_eq!(history.loss[2], f64::INFINITY);
+    }
+
+    #[test]
+    fn no_samples_means_no_epochs() {
+        let (p, report) = train(&TrainConfig::new(10), 0.25, 1.0, &[]);
+        assert_eq!(report.epochs_run, 0);
+        assert_eq!(report.stop_reason, StopReason::MaxEpochs);
+        assert!(report.final_loss.is_nan());
+        assert_eq!(p, 1.0);
+    }
+}
@@ -1,5 +1,35 @@
 // visualization.rs
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::coord::types::RangedCoordusize;
 use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+
+use crate::metrics::{ConfusionMatrix, RocCurve};
+use crate::training::TrainingHistory;
+
+// Calls `$draw(&root, args...)` on an 800×600 drawing area backed by an SVG
+// file if `$path` ends in `.svg`, a PNG otherwise, and saves it.
+macro_rules! render {
+    ($path:expr, $draw:ident($($arg:expr),*)) => {{
+        let path: &Path = $path.as_ref();
+        let is_svg = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+
+        if is_svg {
+            let root = SVGBackend::new(path, (800, 600)).into_drawing_area();
+            $draw(&root, $($arg),*)?;
+            root.present()?;
+        } else {
+            let root = BitMapBackend::new(path, (800, 600)).into_drawing_area();
+            $draw(&root, $($arg),*)?;
+            root.present()?;
+        }
+        Ok(())
+    }};
+}
 
 /// Plots the actual and predicted values and saves the chart as a PNG file.
 ///
@@ -13,12 +43,34 @@ use plotters::prelude::*;
 ///
 /// Returns a Result which is Ok if plotting succeeded, or an error otherwise.
 pub fn plot_predictions_vs_actual(
-    x: &Vec<f64>,
-    y_true: &Vec<f64>,
-    y_pred: &Vec<f64>,
+    x: &[f64],
+    y_true: &[f64],
+    y_pred: &[f64],
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Create a drawing area (800 x 600) for the plot and fill it with white.
-    let root = BitMapBackend::new("prediction_vs_actual.png", (800, 600)).into_drawing_area();
+    plot_predictions_vs_actual_to(x, y_true, y_pred, "prediction_vs_actual.png")
+}
+
+/// Like `plot_predictions_vs_actual`, but saves the chart to `path`: an SVG
+/// file if it ends in `.svg`, a PNG otherwise.
+pub fn plot_predictions_vs_actual_to(
+    x: &[f64],
+    y_true: &[f64],
+    y_pred: &[f64],
+    path: impl AsRef<Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    render!(path, draw_predictions_vs_actual(x, y_true, y_pred))
+}
+
+fn draw_predictions_vs_actual<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    x: &[f64],
+    y_true: &[f64],
+    y_pred: &[f64],
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    // Fill the drawing area with white.
     root.fill(&WHITE)?;
 
     // Calculate ranges from the provided data.
@@ -36,7 +88,7 @@ pub fn plot_predictions_vs_actual(
         .fold(f64::NEG_INFINITY, f64::max);
 
     // Build a 2D cartesian chart with some margins and axis label areas.
-    let mut chart = ChartBuilder::on(&root)
+    let mut chart = ChartBuilder::on(root)
         .caption("Predictions vs Actual Values", ("Arial", 30))
         .margin(20)
         .x_label_area_size(40)
@@ -78,10 +130,258 @@ pub fn plot_predictions_vs_actual(
         .legend(|(x, y)| Circle::new((x, y), 5, BLUE.filled()));
 
     // Draw the legend on the chart.
+    chart.configure_series_labels().border_style(BLACK).draw()?;
+
+    Ok(())
+}
+
+/// Plots the per-epoch training loss (and validation loss, if recorded) of
+/// a training run. Writes an SVG file if `path` ends in `.svg`, a PNG
+/// otherwise.
+///
+/// With `log_scale` the loss axis is logarithmic, which shows the slow tail
+/// of a converging run; epochs with a loss of zero are then left out.
+pub fn plot_loss_curve(
+    history: &TrainingHistory,
+    path: impl AsRef<Path>,
+    log_scale: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    render!(path, draw_loss_curve(history, log_scale))
+}
+
+fn draw_loss_curve<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    history: &TrainingHistory,
+    log_scale: bool,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+
+    // Epochs are numbered from 1; the log axis can only show positive losses
+    let curve = |losses: &[f64]| -> Vec<(usize, f64)> {
+        (1..)
+            .zip(losses.iter().copied())
+            .filter(|&(_, loss)| loss.is_finite() && (!log_scale || loss > 0.0))
+            .collect()
+    };
+    let training = curve(&history.loss);
+    let validation = curve(&history.validation_loss);
+
+    let y_min = training
+        .iter()
+        .chain(validation.iter())
+        .map(|&(_, loss)| loss)
+        .fold(f64::INFINITY, f64::min);
+    let y_max = training
+        .iter()
+        .chain(validation.iter())
+        .map(|&(_, loss)| loss)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if !y_min.is_finite() {
+        return Err("training history has no loss values to plot".into());
+    }
+    // Keep a flat curve from collapsing the axis to a single value
+    let (y_min, y_max) = if y_max > y_min {
+        (y_min, y_max)
+    } else if log_scale {
+        (y_min / 10.0, y_max * 10.0)
+    } else {
+        (y_min - 1.0, y_max + 1.0)
+    };
+    let epochs = 1..history.len().max(2);
+
+    let mut chart = ChartBuilder::on(root);
+    chart
+        .caption("Training Loss", ("Arial", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(60);
+
+    if log_scale {
+        let mut chart = chart.build_cartesian_2d(epochs, (y_min..y_max).log_scale())?;
+        chart
+            .configure_mesh()
+            .x_desc("Epoch")
+            .y_desc("Loss")
+            .y_label_formatter(&|y| format!("{:.0e}", y))
+            .draw()?;
+        draw_loss_series(&mut chart, training, validation)?;
+    } else {
+        let mut chart = chart.build_cartesian_2d(epochs, y_min..y_max)?;
+        chart
+            .configure_mesh()
+            .x_desc("Epoch")
+            .y_desc("Loss")
+            .draw()?;
+        draw_loss_series(&mut chart, training, validation)?;
+    }
+    Ok(())
+}
+
+fn draw_loss_series<'a, DB, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<RangedCoordusize, Y>>,
+    training: Vec<(usize, f64)>,
+    validation: Vec<(usize, f64)>,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB: DrawingBackend + 'a,
+    DB::ErrorType: 'static,
+    Y: Ranged<ValueType = f64>,
+{
+    chart
+        .draw_series(LineSeries::new(training, &BLUE))?
+        .label("Training")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    if !validation.is_empty() {
+        chart
+            .draw_series(LineSeries::new(validation, &RED))?
+            .label("Validation")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+    }
+
     chart
         .configure_series_labels()
-        .border_style(&BLACK)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
         .draw()?;
+    Ok(())
+}
+
+/// Plots a ROC curve against the diagonal of a random classifier, with the
+/// AUC in the caption. Writes SVG or PNG like `plot_loss_curve`.
+pub fn plot_roc_curve(
+    roc: &RocCurve,
+    path: impl AsRef<Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    render!(path, draw_roc_curve(roc))
+}
+
+fn draw_roc_curve<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    roc: &RocCurve,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(format!("ROC Curve (AUC = {:.3})", roc.auc()), ("Arial", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..1.0, 0.0..1.0)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("False positive rate")
+        .y_desc("True positive rate")
+        .draw()?;
+
+    chart.draw_series(DashedLineSeries::new(
+        vec![(0.0, 0.0), (1.0, 1.0)],
+        5,
+        5,
+        BLACK.mix(0.5).into(),
+    ))?;
+    chart
+        .draw_series(LineSeries::new(
+            roc.fpr.iter().copied().zip(roc.tpr.iter().copied()),
+            BLUE.stroke_width(2),
+        ))?
+        .label("Model")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()?;
+    Ok(())
+}
+
+/// Plots a confusion matrix as a heat map with the count in each cell, true
+/// classes down the side and predicted classes across. Writes SVG or PNG
+/// like `plot_loss_curve`.
+pub fn plot_confusion_matrix(
+    matrix: &ConfusionMatrix,
+    path: impl AsRef<Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    render!(path, draw_confusion_matrix(matrix))
+}
+
+fn draw_confusion_matrix<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    matrix: &ConfusionMatrix,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+
+    let k = matrix.n_classes();
+    if k == 0 {
+        return Err("confusion matrix has no classes to plot".into());
+    }
+    let max_count = matrix
+        .counts
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    // A segmented `0..n` has a segment for each of 0..=n; a single class
+    // still gets two so the axes aren't degenerate
+    let segments = 0..k.max(2) - 1;
+
+    let mut chart = ChartBuilder::on(root)
+        .caption("Confusion Matrix", ("Arial", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(segments.clone().into_segmented(), segments.into_segmented())?;
+
+    // Row 0 is drawn at the top, so y positions count down from k - 1
+    let class_label = |value: &SegmentValue<usize>, flip: bool| match *value {
+        SegmentValue::CenterOf(c) if c < k => (if flip { k - 1 - c } else { c }).to_string(),
+        _ => String::new(),
+    };
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_desc("Predicted class")
+        .y_desc("True class")
+        .x_label_formatter(&|v| class_label(v, false))
+        .y_label_formatter(&|v| class_label(v, true))
+        .draw()?;
+
+    for (t, row) in matrix.counts.iter().enumerate() {
+        let y = k - 1 - t;
+        for (p, &count) in row.iter().enumerate() {
+            let intensity = count as f64 / max_count as f64;
+            chart.draw_series(std::iter::once(Rectangle::new(
+                [
+                    (SegmentValue::Exact(p), SegmentValue::Exact(y)),
+                    (SegmentValue::Exact(p + 1), SegmentValue::Exact(y + 1)),
+                ],
+                BLUE.mix(intensity).filled(),
+            )))?;
 
+            let text_color = if intensity > 0.5 { WHITE } else { BLACK };
+            chart.draw_series(std::iter::once(Text::new(
+                count.to_string(),
+                (SegmentValue::CenterOf(p), SegmentValue::CenterOf(y)),
+                ("Arial", 24)
+                    .into_font()
+                    .color(&text_color)
+                    .pos(Pos::new(HPos::Center, VPos::Center)),
+            )))?;
+        }
+    }
     Ok(())
 }